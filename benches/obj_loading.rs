@@ -0,0 +1,134 @@
+//! Times loading OBJ files into meshs, compared with the conversion the crate used before edges were deduplicated.
+//! Run with ```cargo bench --bench obj_loading```.
+
+use ascii_renderer::prelude::*;
+use obj::Obj;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// The old conversion: every polygon's outline is pushed as is (so shared edges appear twice), and every position in the file is checked against every edge to find the ones an object uses.
+fn legacy_meshs(obj: &Obj) -> Vec<Mesh> {
+    fn simple_polygon_to_edges(polygon: &obj::SimplePolygon) -> Vec<(usize, usize)> {
+        let last_vertex_index = polygon.0.last().unwrap().0;
+        polygon
+            .0
+            .iter()
+            .map(|x| x.0)
+            .enumerate()
+            .fold(vec![], |mut accum, (i, v_index)| {
+                if i == 0 {
+                    accum.push((last_vertex_index, v_index))
+                } else {
+                    let prev = accum.last().unwrap().1;
+                    accum.push((prev, v_index));
+                }
+                accum
+            })
+    }
+    fn edges_contains_index(edges: &[(usize, usize)], value: usize) -> bool {
+        edges.iter().fold(false, |accum, (index1, index2)| {
+            if *index1 == value || *index2 == value {
+                true
+            } else {
+                accum
+            }
+        })
+    }
+
+    obj.data
+        .objects
+        .iter()
+        .map(|object| {
+            let edges: Vec<(usize, usize)> = object
+                .groups
+                .iter()
+                .flat_map(|group| group.polys.iter())
+                .flat_map(simple_polygon_to_edges)
+                .collect();
+            let positions: HashMap<usize, Vector3> = obj
+                .data
+                .position
+                .iter()
+                .map(|p| vec3!(p[0], p[1], p[2]))
+                .enumerate()
+                .filter(|(i, _)| edges_contains_index(&edges, *i))
+                .collect();
+            let mut mesh = Mesh::default();
+            *mesh.get_edges_mut() = edges;
+            *mesh.get_verticies_mut() = positions;
+            mesh
+        })
+        .collect()
+}
+
+/// Writes a flat grid of size by size quads, the kind of dense mesh scanned or sculpted models are made of.
+fn write_grid(size: usize) -> PathBuf {
+    let mut source = String::from("o grid\n");
+    for y in 0..=size {
+        for x in 0..=size {
+            source += &format!("v {x} {y} 0\n");
+        }
+    }
+    for y in 0..size {
+        for x in 0..size {
+            let corner = y * (size + 1) + x + 1;
+            source += &format!(
+                "f {} {} {} {}\n",
+                corner,
+                corner + 1,
+                corner + size + 2,
+                corner + size + 1
+            );
+        }
+    }
+    let path = std::env::temp_dir().join(format!("ascii_renderer_grid_{size}.obj"));
+    std::fs::write(&path, source).unwrap();
+    path
+}
+
+/// Runs f at least 3 times and for at least half a second, returning the average time and f's last result.
+fn time<T>(mut f: impl FnMut() -> T) -> (Duration, T) {
+    let start = Instant::now();
+    let mut runs = 0;
+    loop {
+        let result = f();
+        runs += 1;
+        if runs >= 3 && start.elapsed() > Duration::from_millis(500) {
+            return (start.elapsed() / runs, result);
+        }
+    }
+}
+
+fn edge_count(meshs: &[Mesh]) -> usize {
+    meshs.iter().map(|mesh| mesh.get_edges().len()).sum()
+}
+
+fn bench(name: &str, path: &Path) {
+    let (legacy, legacy_meshs) = time(|| legacy_meshs(&Obj::load(path).unwrap()));
+    let (current, meshs) = time(|| -> Vec<Mesh> { AsciiObj::load(path).unwrap().into() });
+    println!(
+        "{name:<16}{:>12.2?}{:>12.2?}{:>9.1}x{:>14}{:>14}",
+        legacy,
+        current,
+        legacy.as_secs_f64() / current.as_secs_f64(),
+        edge_count(&legacy_meshs),
+        edge_count(&meshs),
+    );
+}
+
+fn main() {
+    println!(
+        "{:<16}{:>12}{:>12}{:>10}{:>14}{:>14}",
+        "file", "legacy", "current", "speedup", "legacy edges", "edges"
+    );
+    bench(
+        "face.obj",
+        &Path::new(env!("CARGO_MANIFEST_DIR")).join("face.obj"),
+    );
+    for size in [25, 50, 100] {
+        let path = write_grid(size);
+        bench(&format!("{size}x{size} grid"), &path);
+        std::fs::remove_file(path).ok();
+    }
+}
@@ -0,0 +1,42 @@
+use ascii_renderer::prelude::*;
+
+struct MyLogic {
+    pub renderer: Renderer,
+    pub canvas: BrailleCanvas,
+}
+
+impl Logic for MyLogic {
+    fn process(&mut self, screen_buf: &mut CharBuffer, delta: f32) -> ProcessReturn {
+        // * The cube is drawn to the braille canvas, which is then copied onto the screen buffer
+        self.canvas.clear();
+        self.renderer.draw(&mut self.canvas);
+        self.canvas.copy_to(screen_buf);
+
+        self.renderer.meshs[0].rotation.x += delta * 0.8; //Rotates the cube
+        self.renderer.meshs[0].rotation.y += delta * 1.0;
+        self.renderer.meshs[0].rotation.z += delta * 1.2;
+
+        ProcessReturn::Continue
+    }
+}
+
+fn main() {
+    // * 50 x 25 cells is 100 x 100 dots, but dots are twice as far apart across as down, which the camera corrects for with a cell_aspect of 2
+    let mut camera = Camera::new_auto_aspect(
+        vec3!(0.0, 0.0, -7.0), //Position
+        vec3!(0.0, 0.0, 0.0),  //Rotation
+        0.8,                   //FOV
+    );
+    camera.cell_aspect = 2.0;
+
+    let mut runner = Runner::new(
+        50,
+        25,
+        25,
+        MyLogic {
+            renderer: Renderer::new(vec![ascii_renderer::create_cube()], camera),
+            canvas: BrailleCanvas::new(50, 25),
+        },
+    );
+    runner.run(true);
+}
@@ -0,0 +1,48 @@
+use ascii_renderer::prelude::*;
+
+struct MyLogic {
+    pub renderer: Renderer,
+}
+
+impl Logic for MyLogic {
+    fn process(&mut self, screen_buf: &mut CharBuffer, delta: f32) -> ProcessReturn {
+        screen_buf.fill(' ');
+
+        self.renderer.draw(screen_buf);
+
+        // * Flies forward through the row of cubes, and starts over once it is past the last one
+        self.renderer.camera.position.z += delta * 3.0;
+        if self.renderer.camera.position.z > 20.0 {
+            self.renderer.camera.position.z = -10.0;
+        }
+
+        ProcessReturn::Continue
+    }
+}
+
+fn main() {
+    let cubes = (0..5)
+        .map(|i| {
+            let mut cube = ascii_renderer::create_cube();
+            cube.position = vec3!(0.0, 0.0, i as f32 * 4.0);
+            cube.scale = vec3!(2.0, 2.0, 1.0);
+            cube
+        })
+        .collect();
+    let mut runner = Runner::new(
+        50,
+        50,
+        25,
+        MyLogic {
+            renderer: Renderer::new(
+                cubes,
+                Camera::new(
+                    vec3!(0.3, 0.2, -10.0), //Position, slightly off center so the cubes' edges don't all overlap
+                    vec3!(0.0, 0.0, 0.0),   //Rotation
+                    vec2!(1.2, 1.2),        //FOV
+                ),
+            ),
+        },
+    );
+    runner.run(true);
+}
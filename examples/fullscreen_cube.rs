@@ -0,0 +1,38 @@
+use ascii_renderer::prelude::*;
+
+struct MyLogic {
+    pub renderer: Renderer,
+}
+
+impl Logic for MyLogic {
+    fn process(&mut self, screen_buf: &mut CharBuffer, delta: f32) -> ProcessReturn {
+        screen_buf.fill(' ');
+        self.renderer.draw(screen_buf);
+
+        self.renderer.meshs[0].rotation.x += delta * 0.8; //Rotates the cube
+        self.renderer.meshs[0].rotation.y += delta * 1.0;
+        self.renderer.meshs[0].rotation.z += delta * 1.2;
+
+        ProcessReturn::Continue
+    }
+}
+
+fn main() {
+    // * auto_aspect keeps the cube from stretching when the terminal is resized
+    let mut camera = Camera::new_auto_aspect(
+        vec3!(0.0, 0.0, -7.0), //Position
+        vec3!(0.0, 0.0, 0.0),  //Rotation
+        1.0,                   //Vertical FOV
+    );
+    camera.cell_aspect = 0.5; //Cells are about twice as tall as they are wide without padding
+
+    // * The buffer fills the terminal, and is resized along with it
+    let mut runner = Runner::new_auto_sized(
+        25,
+        MyLogic {
+            renderer: Renderer::new(vec![ascii_renderer::create_cube()], camera),
+        },
+    );
+    runner.buf.padded = false; //Fits twice as many cells across the terminal
+    runner.run(true);
+}
@@ -0,0 +1,79 @@
+use ascii_renderer::prelude::*;
+
+struct MyLogic {
+    pub renderer: Renderer,
+    pub spin: Vector2,
+    pub last_drag: Option<(u16, u16)>,
+    pub quit: bool,
+}
+
+impl Logic for MyLogic {
+    fn event(&mut self, event: &Event) {
+        match event {
+            // * Arrow keys change how fast the cube spins, q quits
+            Event::Key(key) if key.kind == KeyEventKind::Press => match key.code {
+                KeyCode::Left => self.spin.y += 0.5,
+                KeyCode::Right => self.spin.y -= 0.5,
+                KeyCode::Up => self.spin.x += 0.5,
+                KeyCode::Down => self.spin.x -= 0.5,
+                KeyCode::Char(' ') => self.spin = vec2!(0.0, 0.0),
+                KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+                _ => {}
+            },
+            // * Dragging with the left mouse button turns the cube, scrolling zooms
+            Event::Mouse(mouse) => match mouse.kind {
+                MouseEventKind::Down(MouseButton::Left) => {
+                    self.last_drag = Some((mouse.column, mouse.row))
+                }
+                MouseEventKind::Drag(MouseButton::Left) => {
+                    if let Some((column, row)) = self.last_drag {
+                        let cube = &mut self.renderer.meshs[0];
+                        cube.rotation.y -= (mouse.column as f32 - column as f32) * 0.05;
+                        cube.rotation.x += (mouse.row as f32 - row as f32) * 0.1;
+                    }
+                    self.last_drag = Some((mouse.column, mouse.row));
+                }
+                MouseEventKind::Up(_) => self.last_drag = None,
+                MouseEventKind::ScrollUp => self.renderer.camera.position.z += 0.5,
+                MouseEventKind::ScrollDown => self.renderer.camera.position.z -= 0.5,
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+    fn process(&mut self, screen_buf: &mut CharBuffer, delta: f32) -> ProcessReturn {
+        if self.quit {
+            return ProcessReturn::End;
+        }
+        screen_buf.fill(' ');
+        self.renderer.draw(screen_buf);
+
+        self.renderer.meshs[0].rotation.x += self.spin.x * delta;
+        self.renderer.meshs[0].rotation.y += self.spin.y * delta;
+
+        ProcessReturn::Continue
+    }
+}
+
+fn main() {
+    let mut runner = Runner::new(
+        50,
+        50,
+        25,
+        MyLogic {
+            renderer: Renderer::new(
+                vec![ascii_renderer::create_cube()],
+                Camera::new(
+                    vec3!(0.0, 0.0, -7.0), //Position
+                    vec3!(0.0, 0.0, 0.0),  //Rotation
+                    vec2!(1.0, 1.0),       //FOV
+                ),
+            ),
+            spin: vec2!(0.0, 0.0),
+            last_drag: None,
+            quit: false,
+        },
+    );
+    runner.capture_input = true; //Hands keyboard and mouse events to MyLogic::event
+    runner.run(true);
+}
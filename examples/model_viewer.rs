@@ -0,0 +1,87 @@
+use ascii_renderer::prelude::*;
+
+struct MyLogic {
+    pub renderer: Renderer,
+    pub controller: OrbitController,
+    pub quit: bool,
+}
+
+impl Logic for MyLogic {
+    fn event(&mut self, event: &Event) {
+        // * Drag to orbit, scroll to zoom, f to switch between every edge and only the outlines, q to quit
+        if let Event::Key(key) = event {
+            if key.kind == KeyEventKind::Press {
+                match key.code {
+                    KeyCode::Char('q') => self.quit = true,
+                    KeyCode::Char('f') => {
+                        self.renderer.feature_edges = match self.renderer.feature_edges {
+                            Some(_) => None,
+                            None => Some(FeatureEdges::default()),
+                        }
+                    }
+                    _ => (),
+                }
+            }
+        }
+        self.controller.event(event);
+    }
+    fn process(&mut self, screen_buf: &mut CharBuffer, delta: f32) -> ProcessReturn {
+        if self.quit {
+            return ProcessReturn::End;
+        }
+        self.controller.update(&mut self.renderer.camera, delta);
+        screen_buf.fill(' ');
+        self.renderer.draw(screen_buf);
+
+        ProcessReturn::Continue
+    }
+}
+
+fn main() {
+    // * Shows face.obj, or the OBJ or STL file given as an argument
+    let path = std::env::args().nth(1).unwrap_or("face.obj".to_owned());
+    let mut my_meshes: Vec<Mesh> = if path.to_lowercase().ends_with(".stl") {
+        vec![ascii_renderer::stl::load(&path).unwrap()]
+    } else {
+        AsciiObj::load(&path).unwrap().into()
+    };
+    // * Scales the model so that it is about 2 units across, whatever units it was made in
+    let vertices = my_meshes
+        .iter()
+        .flat_map(|mesh| mesh.get_verticies().values());
+    let (min, max) = vertices.fold(
+        (
+            vec3!(f32::MAX, f32::MAX, f32::MAX),
+            vec3!(f32::MIN, f32::MIN, f32::MIN),
+        ),
+        |(min, max), v| {
+            (
+                vec3!(min.x.min(v.x), min.y.min(v.y), min.z.min(v.z)),
+                vec3!(max.x.max(v.x), max.y.max(v.y), max.z.max(v.z)),
+            )
+        },
+    );
+    let size = (max - min).x.max((max - min).y).max((max - min).z);
+    my_meshes.iter_mut().for_each(|mesh| {
+        mesh.scale = vec3!(2.0, 2.0, 2.0) / size;
+        mesh.recenter();
+    });
+
+    let mut controller = OrbitController::new(vec3!(0.0, 0.0, 0.0), 4.0);
+    controller.up = vec3!(0.0, 1.0, 0.0); //OBJ files are +y up, so there's no need to flip the mesh
+    controller.spin = 0.3; //Slowly turns the model until it is dragged around
+
+    let mut runner = Runner::new_auto_sized(
+        25,
+        MyLogic {
+            renderer: Renderer::new(
+                my_meshes,
+                Camera::new_auto_aspect(vec3!(0.0, 0.0, 0.0), vec3!(0.0, 0.0, 0.0), 0.8),
+            ),
+            controller,
+            quit: false,
+        },
+    );
+    runner.capture_input = true;
+    runner.run(true);
+}
@@ -30,14 +30,14 @@ fn main() {
         50,
         25,
         MyLogic {
-            renderer: Renderer {
-                meshs: my_meshes,
-                camera: Camera {
-                    position: vec3!(0.0, 0.0, -3.0),
-                    rotation: vec3!(0.0, 0.0, 0.0),
-                    fov: vec2!(0.8, 0.8),
-                },
-            },
+            renderer: Renderer::new(
+                my_meshes,
+                Camera::new(
+                    vec3!(0.0, 0.0, -3.0), //Position
+                    vec3!(0.0, 0.0, 0.0),  //Rotation
+                    vec2!(0.8, 0.8),       //FOV
+                ),
+            ),
         },
     );
     runner.run(true);
@@ -0,0 +1,39 @@
+use ascii_renderer::prelude::*;
+
+struct MyLogic {
+    pub renderer: Renderer,
+}
+
+impl Logic for MyLogic {
+    fn process(&mut self, screen_buf: &mut CharBuffer, delta: f32) -> ProcessReturn {
+        screen_buf.fill(' ');
+        self.renderer.draw(screen_buf);
+
+        // * Spins the cube around the world's vertical axis, so it keeps its isometric tilt
+        self.renderer.meshs[0].rotate_by(Quaternion::from_axis_angle(vec3!(0.0, 1.0, 0.0), delta));
+
+        ProcessReturn::Continue
+    }
+}
+
+fn main() {
+    let mut cube = ascii_renderer::create_cube();
+    cube.rotation = vec3!(0.6155, std::f32::consts::FRAC_PI_4, 0.0); //The classic isometric angle
+
+    let mut camera = Camera::new_orthographic(
+        vec3!(0.0, 0.0, -10.0), //Position
+        vec3!(0.0, 0.0, 0.0),   //Rotation
+        vec2!(5.0, 5.0),        //Size of the view, in world units
+    );
+    camera.auto_aspect = true;
+
+    let mut runner = Runner::new(
+        40,
+        25,
+        25,
+        MyLogic {
+            renderer: Renderer::new(vec![cube], camera),
+        },
+    );
+    runner.run(true);
+}
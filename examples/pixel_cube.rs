@@ -0,0 +1,47 @@
+use ascii_renderer::prelude::*;
+
+struct MyLogic {
+    pub renderer: Renderer,
+}
+
+// * Implementing Logic<PixelBuffer> rather than Logic makes the runner use a PixelBuffer as its screen
+impl Logic<PixelBuffer> for MyLogic {
+    fn process(&mut self, screen_buf: &mut PixelBuffer, delta: f32) -> ProcessReturn {
+        screen_buf.fill(None);
+        self.renderer.draw(screen_buf);
+
+        for mesh in self.renderer.meshs.iter_mut() {
+            mesh.rotation.x += delta * 0.8; //Rotates the cubes
+            mesh.rotation.y += delta * 1.0;
+            mesh.rotation.z += delta * 1.2;
+        }
+
+        ProcessReturn::Continue
+    }
+}
+
+fn main() {
+    let mut red_cube = ascii_renderer::create_cube();
+    red_cube.position.x = -2.0;
+    red_cube.color = Some(Color::Red);
+    let mut cyan_cube = ascii_renderer::create_cube();
+    cyan_cube.position.x = 2.0;
+    cyan_cube.color = Some(Color::Cyan);
+
+    let mut runner = Runner::new(
+        80,
+        40,
+        25,
+        MyLogic {
+            renderer: Renderer::new(
+                vec![red_cube, cyan_cube],
+                Camera::new(
+                    vec3!(0.0, 0.0, -9.0), //Position
+                    vec3!(0.0, 0.0, 0.0),  //Rotation
+                    vec2!(1.2, 1.2), //FOV. 80 x 40 cells is 80 x 80 pixels, which are about square
+                ),
+            ),
+        },
+    );
+    runner.run(true);
+}
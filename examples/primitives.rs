@@ -0,0 +1,62 @@
+use ascii_renderer::prelude::*;
+use ascii_renderer::primitives;
+
+struct MyLogic {
+    pub renderer: Renderer,
+    pub controller: OrbitController,
+}
+
+impl Logic for MyLogic {
+    fn event(&mut self, event: &Event) {
+        self.controller.event(event); // * Drag to orbit, scroll to zoom
+    }
+    fn process(&mut self, screen_buf: &mut CharBuffer, delta: f32) -> ProcessReturn {
+        self.controller.update(&mut self.renderer.camera, delta);
+        screen_buf.fill(' ');
+        self.renderer.draw(screen_buf);
+
+        ProcessReturn::Continue
+    }
+}
+
+fn main() {
+    let mut shapes = vec![
+        primitives::uv_sphere(1.0, 12, 6),
+        primitives::icosphere(1.0, 1),
+        primitives::cylinder(0.8, 2.0, 10),
+        primitives::cone(1.0, 2.0, 10),
+        primitives::torus(0.8, 0.3, 16, 6),
+        primitives::pyramid(1.6, 1.6),
+        primitives::tetrahedron(1.0),
+        primitives::octahedron(1.0),
+        primitives::dodecahedron(1.0),
+    ];
+    // * Lays the shapes out in a 3 x 3 grid, resting on a floor
+    for (i, shape) in shapes.iter_mut().enumerate() {
+        shape.position = vec3!((i % 3) as f32 * 3.0 - 3.0, 0.0, (i / 3) as f32 * 3.0 - 3.0);
+    }
+    let mut floor = primitives::grid(vec2!(10.0, 10.0), (10, 10));
+    floor.position = vec3!(0.0, 1.2, 0.0);
+    shapes.push(floor);
+    shapes.extend(primitives::axes(1.5));
+
+    let mut controller = OrbitController::new(vec3!(0.0, 0.0, 0.0), 12.0);
+    controller.pitch = -0.5; //Looks down on the shapes from above
+    controller.spin = 0.2;
+
+    let mut renderer = Renderer::new(
+        shapes,
+        Camera::new_auto_aspect(vec3!(0.0, 0.0, 0.0), vec3!(0.0, 0.0, 0.0), 1.0),
+    );
+    renderer.mode = RenderMode::Shaded(Shading::default());
+
+    let mut runner = Runner::new_auto_sized(
+        25,
+        MyLogic {
+            renderer,
+            controller,
+        },
+    );
+    runner.capture_input = true;
+    runner.run(true);
+}
@@ -30,14 +30,14 @@ fn main() {
         50,
         25,
         MyLogic {
-            renderer: Renderer {
-                meshs: vec![ascii_renderer::create_cube()],
-                camera: Camera {
-                    position: vec3!(0.0, 0.0, -7.0),
-                    rotation: vec3!(0.0, 0.0, 0.0),
-                    fov: vec2!(0.8, 0.8),
-                },
-            },
+            renderer: Renderer::new(
+                vec![ascii_renderer::create_cube()],
+                Camera::new(
+                    vec3!(0.0, 0.0, -7.0), //Position
+                    vec3!(0.0, 0.0, 0.0),  //Rotation
+                    vec2!(0.8, 0.8),       //FOV
+                ),
+            ),
             time_offset: 0.0,
         },
     );
@@ -0,0 +1,32 @@
+use ascii_renderer::prelude::*;
+
+struct MyLogic {
+    pub renderer: Renderer,
+}
+
+impl Logic for MyLogic {
+    fn process(&mut self, screen_buf: &mut CharBuffer, delta: f32) -> ProcessReturn {
+        screen_buf.fill(' ');
+
+        self.renderer.draw(screen_buf);
+        self.renderer.meshs[0].rotation.x += delta * 0.8; //Rotates the cube
+        self.renderer.meshs[0].rotation.y += delta * 1.0;
+        self.renderer.meshs[0].rotation.z += delta * 1.2;
+
+        ProcessReturn::Continue
+    }
+}
+
+fn main() {
+    let mut renderer = Renderer::new(
+        vec![ascii_renderer::create_cube()],
+        Camera::new(
+            vec3!(0.0, 0.0, -7.0), //Position
+            vec3!(0.0, 0.0, 0.0),  //Rotation
+            vec2!(0.8, 0.8),       //FOV
+        ),
+    );
+    renderer.mode = RenderMode::Shaded(Shading::default()); // * Fills the cube's faces instead of drawing its edges
+    let mut runner = Runner::new(50, 50, 25, MyLogic { renderer });
+    runner.run(true);
+}
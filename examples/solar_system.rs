@@ -0,0 +1,60 @@
+use ascii_renderer::prelude::*;
+
+struct MyLogic {
+    pub renderer: Renderer,
+    pub planet: NodeId,
+    pub moon_orbit: NodeId,
+}
+
+impl Logic for MyLogic {
+    fn process(&mut self, screen_buf: &mut CharBuffer, delta: f32) -> ProcessReturn {
+        screen_buf.fill(' ');
+        self.renderer.draw(screen_buf);
+
+        // * The moon's orbit is a child of the planet, so it follows the planet around the sun while it turns
+        let scene = &mut self.renderer.scene;
+        let year = Quaternion::from_axis_angle(vec3!(0.0, 1.0, 0.0), delta * 0.5);
+        let month = Quaternion::from_axis_angle(vec3!(0.0, 1.0, 0.0), delta * 2.0);
+        scene[self.planet].transform.rotation = year * scene[self.planet].transform.rotation;
+        scene[self.moon_orbit].transform.rotation =
+            month * scene[self.moon_orbit].transform.rotation;
+
+        ProcessReturn::Continue
+    }
+}
+
+fn main() {
+    let mut scene = Scene::new();
+    let sun = scene.add(None, Node::with_mesh("sun", ascii_renderer::create_cube()));
+
+    // * An empty node at the sun's center acts as the pivot the planet orbits around
+    let planet = scene.add(Some(sun), Node::new("planet orbit"));
+    let mut planet_body = Node::with_mesh("planet", ascii_renderer::create_cube());
+    planet_body.transform.position = vec3!(5.0, 0.0, 0.0);
+    planet_body.transform.scale = vec3!(0.5, 0.5, 0.5);
+    let planet_body = scene.add(Some(planet), planet_body);
+
+    let moon_orbit = scene.add(Some(planet_body), Node::new("moon orbit"));
+    let mut moon = Node::with_mesh("moon", ascii_renderer::create_cube());
+    moon.transform.position = vec3!(3.0, 0.0, 0.0);
+    moon.transform.scale = vec3!(0.4, 0.4, 0.4);
+    scene.add(Some(moon_orbit), moon);
+
+    let mut camera = Camera::new_auto_aspect(vec3!(0.0, 0.0, 0.0), vec3!(0.0, 0.0, 0.0), 1.0);
+    camera.position = vec3!(0.0, -8.0, -12.0);
+    camera.look_at(vec3!(0.0, 0.0, 0.0), vec3!(0.0, -1.0, 0.0));
+    let mut renderer = Renderer::new(vec![], camera);
+    renderer.scene = scene;
+
+    let mut runner = Runner::new(
+        50,
+        50,
+        25,
+        MyLogic {
+            renderer,
+            planet,
+            moon_orbit,
+        },
+    );
+    runner.run(true);
+}
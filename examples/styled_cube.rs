@@ -0,0 +1,51 @@
+use ascii_renderer::prelude::*;
+
+struct MyLogic {
+    pub renderer: Renderer,
+}
+
+// * Implementing Logic<StyledBuffer> rather than Logic makes the runner use a StyledBuffer as its screen
+impl Logic<StyledBuffer> for MyLogic {
+    fn process(&mut self, screen_buf: &mut StyledBuffer, delta: f32) -> ProcessReturn {
+        screen_buf.fill(Cell::default());
+        self.renderer.draw(screen_buf);
+        screen_buf.write_str(
+            1,
+            0,
+            &format!("fps: {}", (1.0 / delta).round()),
+            Style::new(
+                Some(Color::Black),
+                Some(Color::White),
+                Attribute::Bold.into(),
+            ),
+        );
+
+        self.renderer.meshs[0].rotation.x += delta * 0.8; //Rotates the cube
+        self.renderer.meshs[0].rotation.y += delta * 1.0;
+        self.renderer.meshs[0].rotation.z += delta * 1.2;
+
+        ProcessReturn::Continue
+    }
+}
+
+fn main() {
+    let mut cube = ascii_renderer::create_cube();
+    cube.color = Some(Color::Magenta);
+
+    let mut runner = Runner::new(
+        50,
+        50,
+        25,
+        MyLogic {
+            renderer: Renderer::new(
+                vec![cube],
+                Camera::new(
+                    vec3!(0.0, 0.0, -7.0), //Position
+                    vec3!(0.0, 0.0, 0.0),  //Rotation
+                    vec2!(1.0, 1.0),       //FOV
+                ),
+            ),
+        },
+    );
+    runner.run(true);
+}
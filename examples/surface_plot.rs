@@ -0,0 +1,57 @@
+use ascii_renderer::plot::{self, Axes, IsoLines};
+use ascii_renderer::prelude::*;
+
+struct MyLogic {
+    pub renderer: Renderer,
+    pub controller: OrbitController,
+    pub time: f32,
+}
+
+impl Logic for MyLogic {
+    fn event(&mut self, event: &Event) {
+        self.controller.event(event); // * Drag to orbit, scroll to zoom
+    }
+    fn process(&mut self, screen_buf: &mut CharBuffer, delta: f32) -> ProcessReturn {
+        // * Regenerates the surface every frame, so the ripples move outwards
+        self.time += delta;
+        let time = self.time;
+        self.renderer.meshs[0] = plot::surface(
+            |x, y| ((x * x + y * y).sqrt() * 2.0 - time * 3.0).sin() * 0.5,
+            -3.0..3.0,
+            -3.0..3.0,
+            (30, 30),
+            IsoLines::Both,
+        );
+
+        self.controller.update(&mut self.renderer.camera, delta);
+        screen_buf.fill(' ');
+        self.renderer.draw(screen_buf);
+
+        ProcessReturn::Continue
+    }
+}
+
+fn main() {
+    let axes = Axes::new(-3.0..3.0, -3.0..3.0, -1.0..1.0);
+    let mut camera = Camera::new_auto_aspect(vec3!(0.0, 0.0, 0.0), vec3!(0.0, 0.0, 0.0), 0.9);
+    camera.cell_aspect = 0.5; //Labels are easier to read without padding between their chars
+
+    let mut renderer = Renderer::new(vec![Mesh::default(), axes.mesh()], camera);
+    renderer.labels = axes.labels();
+
+    let mut controller = OrbitController::new(vec3!(0.0, 0.0, 0.0), 12.0);
+    controller.pitch = -0.6; //Looks down on the plot from above
+    controller.yaw = -0.6;
+
+    let mut runner = Runner::new_auto_sized(
+        25,
+        MyLogic {
+            renderer,
+            controller,
+            time: 0.0,
+        },
+    );
+    runner.buf.padded = false;
+    runner.capture_input = true;
+    runner.run(true);
+}
@@ -0,0 +1,128 @@
+use super::canvas::Canvas;
+use super::char_buffer::CharBuffer;
+
+/// The bit of a braille pattern for each dot of a cell, indexed by [y][x].
+const DOT_BITS: [[u8; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+///A canvas with 2 x 4 dots in every cell, shown using the Unicode braille patterns (U+2800 to U+28FF). Lines, triangles and meshs can be drawn to it the same way as to a CharBuffer, but at a much higher resolution.
+/// Draw to it, then copy it onto the runner's CharBuffer to show it.
+/// Because the CharBuffer's ```Display``` puts a space after every char, the dots end up twice as far apart across as they are down, so a camera drawing to a braille canvas with as many dots across as down should have a horizontal fov twice its vertical fov, or have auto_aspect set with a cell_aspect of 2.0.
+/// # Example
+/// ```
+/// # use ascii_renderer::prelude::*;
+/// let mut canvas = BrailleCanvas::new(2, 1); //2 x 1 cells, which is 4 x 4 dots
+/// canvas.draw_line(Line {
+///     stroke: Stroke::Char('+'),
+///     points: (vec2!(0.0, 0.0), vec2!(3.0, 3.0)),
+/// });
+/// let mut screen_buf = CharBuffer::new(2, 1);
+/// canvas.copy_to(&mut screen_buf);
+/// assert_eq!(&screen_buf.to_string(), "⠑ ⢄ \n");
+/// ```
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct BrailleCanvas {
+    ///The raised dots of every cell, as the low byte of its braille pattern.
+    pub data: Vec<Vec<u8>>,
+    ///The dimensions in cells, not dots.
+    pub dimensions: (usize, usize),
+}
+
+impl BrailleCanvas {
+    pub fn new(width: usize, height: usize) -> Self {
+        //! Creates a canvas that is width x height cells, meaning (width * 2) x (height * 4) dots.
+        BrailleCanvas {
+            data: vec![vec![0; width]; height],
+            dimensions: (width, height),
+        }
+    }
+    pub fn get_dot(&self, x: usize, y: usize) -> Option<bool> {
+        let cell = self.data.get(y / 4)?.get(x / 2)?;
+        Some(cell & DOT_BITS[y % 4][x % 2] != 0)
+    }
+    pub fn set_dot(&mut self, x: usize, y: usize, value: bool) {
+        //! Raises or lowers a dot. Out of range dots are ignored.
+        if let Some(cell) = self.data.get_mut(y / 4).and_then(|row| row.get_mut(x / 2)) {
+            if value {
+                *cell |= DOT_BITS[y % 4][x % 2];
+            } else {
+                *cell &= !DOT_BITS[y % 4][x % 2];
+            }
+        }
+    }
+    pub fn get_char(&self, x: usize, y: usize) -> Option<char> {
+        //! Gets the braille char for the cell at x, y. Empty cells are a space rather than the blank braille pattern, which some fonts draw at a different width.
+        let cell = *self.data.get(y)?.get(x)?;
+        if cell == 0 {
+            return Some(' ');
+        }
+        char::from_u32(0x2800 + cell as u32)
+    }
+    pub fn clear(&mut self) {
+        //! Lowers every dot
+        for row in self.data.iter_mut() {
+            for item in row.iter_mut() {
+                *item = 0;
+            }
+        }
+    }
+    pub fn copy_to(&self, buffer: &mut CharBuffer) {
+        //! Writes the canvas to the top left of a CharBuffer, one char per cell. Cells that don't fit on the buffer are skipped.
+        for y in 0..self.dimensions.1 {
+            for x in 0..self.dimensions.0 {
+                let _ = buffer.set_char(x, y, self.get_char(x, y).unwrap());
+            }
+        }
+    }
+}
+
+impl Canvas for BrailleCanvas {
+    fn dimensions(&self) -> (usize, usize) {
+        (self.dimensions.0 * 2, self.dimensions.1 * 4)
+    }
+    fn plot(&mut self, x: usize, y: usize, char: char) {
+        self.set_dot(x, y, char != ' ');
+    }
+}
+
+impl std::convert::From<&BrailleCanvas> for CharBuffer {
+    fn from(canvas: &BrailleCanvas) -> Self {
+        let mut buffer = CharBuffer::new(canvas.dimensions.0, canvas.dimensions.1);
+        canvas.copy_to(&mut buffer);
+        buffer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dot_patterns() {
+        let mut canvas = BrailleCanvas::new(1, 1);
+        assert_eq!(canvas.get_char(0, 0), Some(' '));
+        canvas.set_dot(0, 0, true);
+        assert_eq!(canvas.get_char(0, 0), Some('⠁'));
+        canvas.set_dot(1, 3, true);
+        assert_eq!(canvas.get_char(0, 0), Some('⢁'));
+        for y in 0..4 {
+            for x in 0..2 {
+                canvas.set_dot(x, y, true);
+            }
+        }
+        assert_eq!(canvas.get_char(0, 0), Some('⣿'));
+        canvas.set_dot(0, 0, false);
+        assert_eq!(canvas.get_dot(0, 0), Some(false));
+        assert_eq!(canvas.get_char(0, 0), Some('⣾'));
+        assert_eq!(canvas.get_dot(2, 0), None);
+    }
+
+    #[test]
+    fn conversion_to_char_buffer() {
+        let mut canvas = BrailleCanvas::new(2, 2);
+        canvas.plot(3, 7, '+');
+        canvas.plot(0, 0, '+');
+        canvas.plot(0, 0, ' ');
+        canvas.plot(100, 100, '+');
+        assert_eq!(&CharBuffer::from(&canvas).to_string(), "    \n  ⢀ \n");
+    }
+}
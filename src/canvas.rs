@@ -0,0 +1,71 @@
+use super::char_buffer::CharBuffer;
+use super::depth_buffer::DepthBuffer;
+use super::line::{self, Line};
+use super::triangle::{self, Triangle};
+use crossterm::style::Color;
+
+///Anything that lines, triangles and meshs can be drawn to. The coords passed to a canvas are in its own units, which are cells for a CharBuffer but dots for a BrailleCanvas.
+pub trait Canvas {
+    ///The number of addressable points across and down the canvas.
+    fn dimensions(&self) -> (usize, usize);
+    ///Draws a char at the given coords. Out of range coords are ignored. Canvases that can't show chars decide for themselves what a char means, e.g. a BrailleCanvas turns a dot off for ```' '``` and on for anything else.
+    fn plot(&mut self, x: usize, y: usize, char: char);
+    ///Gets the char at the given coords, if the canvas stores chars. Used to join sloped lines.
+    fn char_at(&self, _x: usize, _y: usize) -> Option<char> {
+        None
+    }
+    ///Sets the color that following draws should use, for canvases that support color. The renderer calls this with each mesh's color before drawing it, and with None once it is done, so that None should go back to the canvas's own default.
+    fn set_color(&mut self, _color: Option<Color>) {}
+    fn draw_line(&mut self, line: Line) {
+        //! Draws an individual line to the canvas. Whatever part of the line is off the canvas is clipped away.
+        line::draw_line(self, line);
+    }
+    fn draw_lines(&mut self, lines: Vec<Line>) {
+        //! Draws lines to the canvas. The first lines in the vector will be drawn first.
+        //! # Example
+        //! ```
+        //! # use ascii_renderer::prelude::*;
+        //! let mut buf = CharBuffer::new(10, 10);
+        //! let lines = vec![
+        //!     Line {
+        //!         stroke: Stroke::Char('+'),
+        //!         points: (vec2!(2.0, 2.0), vec2!(8.0, 8.0)),
+        //!     },
+        //!     Line {
+        //!         stroke: Stroke::Slope,
+        //!         points: (vec2!(2.0, 8.0), vec2!(8.0, 2.0)),
+        //!     },
+        //! ];
+        //! buf.draw_lines(lines);
+        //! println!("{buf}");
+        //! ```
+        for line in lines {
+            self.draw_line(line);
+        }
+    }
+    fn draw_line_depth_tested(
+        &mut self,
+        line: Line,
+        depths: (f32, f32),
+        depth_buffer: &mut DepthBuffer,
+    ) {
+        //! Draws a line where each end has a depth, which is linearly interpolated along the line. A char is only written if it is closer than the depth already in the depth buffer.
+        line::draw_line_depth_tested(self, line, depths, depth_buffer);
+    }
+    fn draw_triangle_depth_tested(&mut self, triangle: Triangle, depth_buffer: &mut DepthBuffer) {
+        //! Fills every point whose center lies within the triangle, as long as the interpolated depth there is closer than what the depth buffer already holds.
+        triangle::draw_triangle_depth_tested(self, triangle, depth_buffer);
+    }
+}
+
+impl Canvas for CharBuffer {
+    fn dimensions(&self) -> (usize, usize) {
+        self.dimensions
+    }
+    fn plot(&mut self, x: usize, y: usize, char: char) {
+        let _ = self.set_char(x, y, char);
+    }
+    fn char_at(&self, x: usize, y: usize) -> Option<char> {
+        self.get_char(x, y)
+    }
+}
@@ -1,16 +1,19 @@
 ///The buffer used by the runner and mutated by the logic struct. Just a wrapper around a 2D char vector.
+/// When displayed, every char is followed by a space so that cells come out roughly square in most fonts. Setting ```padded``` to false leaves the space out, which fits twice as many cells across the terminal, but cells are then about twice as tall as they are wide (see ```Camera::cell_aspect```).
 /// # Example
 /// ```
+/// # use ascii_renderer::prelude::*;
 /// let mut buf = CharBuffer::new(3, 3);
 /// buf.set_char(0, 0, 'n').unwrap();
 /// buf.set_char(2, 1, 'x').unwrap();
 /// buf.set_char(2, 2, 'z').unwrap();
 /// assert_eq!(&buf.to_string(), "n     \n    x \n    z \n");
 ///```
-#[derive(Debug, Clone, PartialEq, PartialOrd)]
+#[derive(Debug, PartialEq, PartialOrd)]
 pub struct CharBuffer {
     pub data: Vec<Vec<char>>,
     pub dimensions: (usize, usize),
+    pub padded: bool,
 }
 
 #[derive(Debug)]
@@ -24,10 +27,11 @@ impl CharBuffer {
         CharBuffer {
             data: vec![vec![' '; width]; height],
             dimensions: (width, height),
+            padded: true,
         }
     }
     pub fn get_char(&self, x: usize, y: usize) -> Option<char> {
-        self.data.get(y)?.get(x).map(|x| *x)
+        self.data.get(y)?.get(x).copied()
     }
     pub fn set_char(&mut self, x: usize, y: usize, value: char) -> Result<(), CharBufferError> {
         let row = self.data.get_mut(y);
@@ -44,6 +48,17 @@ impl CharBuffer {
 
         Ok(())
     }
+    pub fn resize(&mut self, width: usize, height: usize, clear: bool) {
+        //! Changes the dimensions of the buffer. If clear is false, whatever lies within both the old and new dimensions is kept and any new cells are blank, otherwise the whole buffer is blank afterwards.
+        if clear {
+            self.fill(' ');
+        }
+        self.data.resize(height, vec![' '; width]);
+        for row in self.data.iter_mut() {
+            row.resize(width, ' ');
+        }
+        self.dimensions = (width, height);
+    }
     pub fn fill(&mut self, char: char) {
         //! Filles the char buffer with the given char
         for row in self.data.iter_mut() {
@@ -54,20 +69,35 @@ impl CharBuffer {
     }
 }
 
+impl Clone for CharBuffer {
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data.clone(),
+            dimensions: self.dimensions,
+            padded: self.padded,
+        }
+    }
+    fn clone_from(&mut self, source: &Self) {
+        //! Reuses the rows that are already allocated, so that the runner can keep a copy of the last frame without allocating a new one every frame.
+        self.data.clone_from(&source.data);
+        self.dimensions = source.dimensions;
+        self.padded = source.padded;
+    }
+}
+
 impl std::fmt::Display for CharBuffer {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            self.data
-                .iter()
-                .map(|x| [
-                    x.iter().map(|x| [x, &' ']).flatten().collect::<String>(),
-                    "\n".to_owned()
-                ])
-                .flatten()
-                .collect::<String>()
-        )
+        use std::fmt::Write;
+        for row in self.data.iter() {
+            for &char in row.iter() {
+                f.write_char(char)?;
+                if self.padded {
+                    f.write_char(' ')?;
+                }
+            }
+            f.write_char('\n')?;
+        }
+        Ok(())
     }
 }
 
@@ -96,6 +126,18 @@ mod tests {
         buf.get_char(2, 2).unwrap();
     }
 
+    #[test]
+    fn resizing() {
+        let mut buf = CharBuffer::new(3, 2);
+        buf.set_char(0, 0, 'a').unwrap();
+        buf.set_char(2, 1, 'b').unwrap();
+        buf.resize(2, 3, false);
+        assert_eq!(buf.dimensions, (2, 3));
+        assert_eq!(&buf.to_string(), "a   \n    \n    \n");
+        buf.resize(4, 1, true);
+        assert_eq!(&buf.to_string(), "        \n");
+    }
+
     #[test]
     fn char_buf_to_string() {
         let mut buf = CharBuffer::new(3, 3);
@@ -103,5 +145,7 @@ mod tests {
         buf.set_char(2, 1, 'x').unwrap();
         buf.set_char(2, 2, 'z').unwrap();
         assert_eq!(&buf.to_string(), "n     \n    x \n    z \n");
+        buf.padded = false;
+        assert_eq!(&buf.to_string(), "n  \n  x\n  z\n");
     }
 }
@@ -0,0 +1,399 @@
+use super::rendering::Camera;
+use super::{vec3, Vector3};
+use crossterm::event::{Event, KeyCode, KeyEventKind, MouseButton, MouseEventKind};
+use std::collections::HashMap;
+
+/// How long a key counts as held after it is first pressed, on terminals that don't report key releases. Long enough to last until the terminal starts repeating the key.
+const INITIAL_HOLD: f32 = 0.6;
+/// How long a key counts as held after each repeat, on terminals that don't report key releases.
+const REPEAT_HOLD: f32 = 0.1;
+/// How far up or down a controller can look, just short of straight along up so that the camera never flips over.
+const MAX_PITCH: f32 = std::f32::consts::FRAC_PI_2 - 0.01;
+
+///Something that moves a camera around in response to input. Hand it every event from ```Logic::event()``` and call ```update()``` once per frame, before drawing.
+/// # Example
+/// ```no_run
+/// # use ascii_renderer::prelude::*;
+/// struct Viewer {
+///     renderer: Renderer,
+///     controller: OrbitController,
+/// }
+///
+/// impl Logic for Viewer {
+///     fn event(&mut self, event: &Event) {
+///         self.controller.event(event);
+///     }
+///     fn process(&mut self, screen_buf: &mut CharBuffer, delta: f32) -> ProcessReturn {
+///         self.controller.update(&mut self.renderer.camera, delta);
+///         screen_buf.fill(' ');
+///         self.renderer.draw(screen_buf);
+///         ProcessReturn::Continue
+///     }
+/// }
+///
+/// let mut runner = Runner::new_auto_sized(
+///     25,
+///     Viewer {
+///         renderer: Renderer::new(
+///             vec![ascii_renderer::create_cube()],
+///             Camera::new_auto_aspect(vec3!(0.0, 0.0, 0.0), vec3!(0.0, 0.0, 0.0), 1.0),
+///         ),
+///         controller: OrbitController::new(vec3!(0.0, 0.0, 0.0), 7.0),
+///     },
+/// );
+/// runner.capture_input = true;
+/// runner.run(true);
+/// ```
+pub trait CameraController {
+    ///Takes in an input event. Nothing moves until the next ```update()```.
+    fn event(&mut self, _event: &Event) {}
+    ///Moves and turns the camera for a frame that took delta seconds.
+    fn update(&mut self, camera: &mut Camera, delta: f32);
+}
+
+///Swings the camera around a target, always facing it, like a turntable. Good for looking over a model.
+/// Dragging with the left mouse button (or the arrow keys) orbits, and scrolling (or + and -) zooms in and out.
+/// The camera's position is worked out from the target, yaw, pitch and distance on every update, so move the camera by changing those rather than the camera itself.
+#[derive(Debug, Clone)]
+pub struct OrbitController {
+    pub target: Vector3,
+    pub distance: f32,
+    ///The angle around up in radians. At 0.0 the camera looks along +z (for the default up), and larger angles swing it to its left, so that the target seems to turn to the right.
+    pub yaw: f32,
+    ///The angle above or below the target in radians, where positive pitches look up at it from below. Kept within just under pi / 2 either way.
+    pub pitch: f32,
+    ///Which way is up on screen, -y by default (see ```Camera::look_at()```).
+    pub up: Vector3,
+    pub min_distance: f32,
+    pub max_distance: f32,
+    ///How far dragging turns the camera, in radians per row. A column counts as half a row, since cells are about twice as tall as they are wide.
+    pub sensitivity: f32,
+    ///How far each press of an arrow key turns the camera, in radians.
+    pub key_step: f32,
+    ///How much each scroll (or + and - press) changes the distance by, as a fraction of the distance.
+    pub zoom_step: f32,
+    ///How fast the camera swings around on its own, in radians per second.
+    pub spin: f32,
+    drag: Drag,
+}
+
+impl OrbitController {
+    pub fn new(target: Vector3, distance: f32) -> Self {
+        OrbitController {
+            target,
+            distance,
+            yaw: 0.0,
+            pitch: 0.0,
+            up: vec3!(0.0, -1.0, 0.0),
+            min_distance: 0.1,
+            max_distance: f32::INFINITY,
+            sensitivity: 0.1,
+            key_step: 0.1,
+            zoom_step: 0.1,
+            spin: 0.0,
+            drag: Drag::default(),
+        }
+    }
+    pub fn zoom(&mut self, steps: f32) {
+        //! Moves in by the given number of zoom steps, or out for negative steps.
+        self.distance *= (1.0 - self.zoom_step).powf(steps);
+        self.distance = self.distance.clamp(self.min_distance, self.max_distance);
+    }
+    pub fn camera_position(&self) -> Vector3 {
+        //! Where the camera is put on the next update.
+        self.target - direction(self.up, self.yaw, self.pitch) * self.distance
+    }
+}
+
+impl CameraController for OrbitController {
+    fn event(&mut self, event: &Event) {
+        if let Some((columns, rows)) = self.drag.event(event) {
+            self.yaw += columns * self.sensitivity * 0.5;
+            self.pitch -= rows * self.sensitivity;
+        }
+        match event {
+            Event::Key(key) if key.kind != KeyEventKind::Release => match key.code {
+                KeyCode::Left => self.yaw -= self.key_step,
+                KeyCode::Right => self.yaw += self.key_step,
+                KeyCode::Up => self.pitch += self.key_step,
+                KeyCode::Down => self.pitch -= self.key_step,
+                KeyCode::Char('+') | KeyCode::Char('=') => self.zoom(1.0),
+                KeyCode::Char('-') => self.zoom(-1.0),
+                _ => {}
+            },
+            Event::Mouse(mouse) => match mouse.kind {
+                MouseEventKind::ScrollUp => self.zoom(1.0),
+                MouseEventKind::ScrollDown => self.zoom(-1.0),
+                _ => {}
+            },
+            _ => {}
+        }
+        self.pitch = self.pitch.clamp(-MAX_PITCH, MAX_PITCH);
+    }
+    fn update(&mut self, camera: &mut Camera, delta: f32) {
+        self.yaw += self.spin * delta;
+        self.pitch = self.pitch.clamp(-MAX_PITCH, MAX_PITCH);
+        self.distance = self.distance.clamp(self.min_distance, self.max_distance);
+        camera.position = self.camera_position();
+        camera.look_at(self.target, self.up);
+    }
+}
+
+///Flies the camera around freely, like a first person game. W, A, S and D move forward, left, back and right, E and Q move up and down, and dragging with the left mouse button (or holding the arrow keys) looks around.
+/// Terminals that support it report when keys are let go, but on the rest a key counts as held for a moment after each press or repeat, so the camera may drift on briefly after a key is let go.
+/// The camera's position and rotation are replaced on every update, so move the camera by changing the controller's position, yaw and pitch rather than the camera itself.
+#[derive(Debug, Clone)]
+pub struct FlyController {
+    pub position: Vector3,
+    ///The angle around up in radians. At 0.0 the camera looks along +z (for the default up), and larger angles turn it to the right.
+    pub yaw: f32,
+    ///The angle above or below level in radians, where positive pitches look up. Kept within just under pi / 2 either way.
+    pub pitch: f32,
+    ///Which way is up on screen, -y by default (see ```Camera::look_at()```).
+    pub up: Vector3,
+    ///How fast the camera moves, in world units per second.
+    pub speed: f32,
+    ///How fast the arrow keys turn the camera, in radians per second.
+    pub turn_speed: f32,
+    ///How far dragging turns the camera, in radians per row. A column counts as half a row.
+    pub sensitivity: f32,
+    held: HashMap<KeyCode, f32>,
+    releases_reported: bool,
+    drag: Drag,
+}
+
+impl FlyController {
+    pub fn new(position: Vector3) -> Self {
+        FlyController {
+            position,
+            yaw: 0.0,
+            pitch: 0.0,
+            up: vec3!(0.0, -1.0, 0.0),
+            speed: 3.0,
+            turn_speed: 1.5,
+            sensitivity: 0.05,
+            held: HashMap::new(),
+            releases_reported: false,
+            drag: Drag::default(),
+        }
+    }
+    pub fn forward(&self) -> Vector3 {
+        //! The direction the camera faces.
+        direction(self.up, self.yaw, self.pitch)
+    }
+    pub fn is_held(&self, code: KeyCode) -> bool {
+        //! Whether the key currently counts as held. Letters are always lowercase.
+        self.held.contains_key(&code)
+    }
+}
+
+impl CameraController for FlyController {
+    fn event(&mut self, event: &Event) {
+        if let Some((columns, rows)) = self.drag.event(event) {
+            self.yaw += columns * self.sensitivity * 0.5;
+            self.pitch -= rows * self.sensitivity;
+            self.pitch = self.pitch.clamp(-MAX_PITCH, MAX_PITCH);
+        }
+        match event {
+            Event::Key(key) => {
+                let code = match key.code {
+                    KeyCode::Char(char) => KeyCode::Char(char.to_ascii_lowercase()),
+                    code => code,
+                };
+                if key.kind == KeyEventKind::Release {
+                    self.releases_reported = true;
+                    self.held.remove(&code);
+                } else if self.releases_reported {
+                    self.held.insert(code, f32::INFINITY);
+                } else {
+                    let hold = if self.held.contains_key(&code) {
+                        REPEAT_HOLD
+                    } else {
+                        INITIAL_HOLD
+                    };
+                    self.held.insert(code, hold);
+                }
+            }
+            Event::FocusLost => self.held.clear(),
+            _ => {}
+        }
+    }
+    fn update(&mut self, camera: &mut Camera, delta: f32) {
+        let axis = |held: &HashMap<KeyCode, f32>, positive: KeyCode, negative: KeyCode| {
+            held.contains_key(&positive) as i32 as f32 - held.contains_key(&negative) as i32 as f32
+        };
+        self.yaw += axis(&self.held, KeyCode::Right, KeyCode::Left) * self.turn_speed * delta;
+        self.pitch += axis(&self.held, KeyCode::Up, KeyCode::Down) * self.turn_speed * delta;
+        self.pitch = self.pitch.clamp(-MAX_PITCH, MAX_PITCH);
+
+        let forward = self.forward();
+        let right = forward.cross(self.up).normalize();
+        let movement = forward * axis(&self.held, KeyCode::Char('w'), KeyCode::Char('s'))
+            + right * axis(&self.held, KeyCode::Char('d'), KeyCode::Char('a'))
+            + self.up.normalize() * axis(&self.held, KeyCode::Char('e'), KeyCode::Char('q'));
+        self.position += movement.normalize() * self.speed * delta;
+
+        self.held.retain(|_, time_left| {
+            *time_left -= delta;
+            *time_left > 0.0
+        });
+
+        camera.position = self.position;
+        camera.look_at(self.position + forward, self.up);
+    }
+}
+
+/// Follows left mouse button drags.
+#[derive(Debug, Clone, Default)]
+struct Drag {
+    last: Option<(u16, u16)>,
+}
+
+impl Drag {
+    /// Gets how far the mouse was dragged, in (columns, rows), if the event was part of a drag.
+    fn event(&mut self, event: &Event) -> Option<(f32, f32)> {
+        let Event::Mouse(mouse) = event else {
+            return None;
+        };
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                self.last = Some((mouse.column, mouse.row));
+                None
+            }
+            MouseEventKind::Drag(MouseButton::Left) => {
+                let last = self.last.replace((mouse.column, mouse.row))?;
+                Some((
+                    mouse.column as f32 - last.0 as f32,
+                    mouse.row as f32 - last.1 as f32,
+                ))
+            }
+            MouseEventKind::Up(_) => {
+                self.last = None;
+                None
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The direction at the given yaw and pitch around up, where a yaw and pitch of 0.0 is whichever way is closest to +z (or +x if up is along z).
+fn direction(up: Vector3, yaw: f32, pitch: f32) -> Vector3 {
+    let up = up.normalize();
+    let reference = if up.z.abs() < 0.9 {
+        vec3!(0.0, 0.0, 1.0)
+    } else {
+        vec3!(1.0, 0.0, 0.0)
+    };
+    let forward = (reference - up * reference.dot(up)).normalize();
+    let right = forward.cross(up);
+    (forward * yaw.cos() + right * yaw.sin()) * pitch.cos() + up * pitch.sin()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{vec2, Vector2};
+    use crossterm::event::{KeyEvent, KeyEventState, KeyModifiers, MouseEvent};
+
+    fn camera() -> Camera {
+        Camera::new(vec3!(0.0, 0.0, 0.0), vec3!(0.0, 0.0, 0.0), vec2!(1.0, 1.0))
+    }
+
+    fn key(code: KeyCode, kind: KeyEventKind) -> Event {
+        Event::Key(KeyEvent {
+            code,
+            modifiers: KeyModifiers::NONE,
+            kind,
+            state: KeyEventState::NONE,
+        })
+    }
+
+    fn mouse(kind: MouseEventKind, column: u16, row: u16) -> Event {
+        Event::Mouse(MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        })
+    }
+
+    #[test]
+    fn orbiting() {
+        let target = vec3!(1.0, 2.0, 3.0);
+        let mut controller = OrbitController::new(target, 5.0);
+        let mut camera = camera();
+        controller.update(&mut camera, 0.1);
+        // Starts out looking along +z, like a camera with no rotation
+        assert!(camera.position.approx_eq(vec3!(1.0, 2.0, -2.0), 1e-4));
+        assert!(camera.rotation.approx_eq(vec3!(0.0, 0.0, 0.0), 1e-4));
+
+        // Dragging right swings the camera to the left of the target
+        controller.event(&mouse(MouseEventKind::Down(MouseButton::Left), 10, 10));
+        controller.event(&mouse(MouseEventKind::Drag(MouseButton::Left), 20, 10));
+        controller.update(&mut camera, 0.1);
+        assert!(camera.position.x < target.x);
+        // Dragging down lifts it above the target, which is -y
+        controller.event(&mouse(MouseEventKind::Drag(MouseButton::Left), 20, 15));
+        controller.update(&mut camera, 0.1);
+        assert!(camera.position.y < target.y);
+        assert!(camera
+            .to_view_space(target)
+            .approx_eq(vec3!(0.0, 0.0, 5.0), 1e-4));
+
+        // Drags only count while the button is down
+        controller.event(&mouse(MouseEventKind::Up(MouseButton::Left), 20, 15));
+        let yaw = controller.yaw;
+        controller.event(&mouse(MouseEventKind::Drag(MouseButton::Left), 40, 15));
+        assert_eq!(controller.yaw, yaw);
+
+        controller.event(&mouse(MouseEventKind::ScrollUp, 0, 0));
+        assert!((controller.distance - 4.5).abs() < 1e-4);
+        controller.max_distance = 6.0;
+        controller.zoom(-10.0);
+        assert_eq!(controller.distance, 6.0);
+
+        for _ in 0..100 {
+            controller.event(&key(KeyCode::Up, KeyEventKind::Press));
+        }
+        assert_eq!(controller.pitch, MAX_PITCH);
+    }
+
+    #[test]
+    fn flying() {
+        let mut controller = FlyController::new(vec3!(0.0, 0.0, 0.0));
+        let mut camera = camera();
+        controller.event(&key(KeyCode::Char('W'), KeyEventKind::Press));
+        controller.update(&mut camera, 0.5);
+        assert!(camera.position.approx_eq(vec3!(0.0, 0.0, 1.5), 1e-4));
+        // Without release events, the key stops counting as held after a moment
+        controller.update(&mut camera, 0.5);
+        controller.update(&mut camera, 0.5);
+        assert!(camera.position.approx_eq(vec3!(0.0, 0.0, 3.0), 1e-4));
+
+        // Once releases are seen, keys are held until they are let go
+        controller.event(&key(KeyCode::Char('w'), KeyEventKind::Release));
+        controller.event(&key(KeyCode::Char('e'), KeyEventKind::Press));
+        for _ in 0..4 {
+            controller.update(&mut camera, 0.5);
+        }
+        assert!(camera.position.approx_eq(vec3!(0.0, -6.0, 3.0), 1e-4));
+        controller.event(&key(KeyCode::Char('e'), KeyEventKind::Release));
+        assert!(!controller.is_held(KeyCode::Char('e')));
+
+        // Turning right and then moving forward heads along +x
+        controller.event(&key(KeyCode::Right, KeyEventKind::Press));
+        controller.update(
+            &mut camera,
+            std::f32::consts::FRAC_PI_2 / controller.turn_speed,
+        );
+        controller.event(&key(KeyCode::Right, KeyEventKind::Release));
+        controller.event(&key(KeyCode::Char('w'), KeyEventKind::Press));
+        controller.update(&mut camera, 1.0);
+        assert!(camera.position.approx_eq(vec3!(3.0, -6.0, 3.0), 1e-4));
+        let ahead = camera.to_view_space(camera.position + vec3!(1.0, 0.0, 0.0));
+        assert!(ahead.approx_eq(vec3!(0.0, 0.0, 1.0), 1e-4));
+
+        controller.event(&Event::FocusLost);
+        assert!(!controller.is_held(KeyCode::Char('w')));
+    }
+}
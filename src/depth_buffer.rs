@@ -0,0 +1,75 @@
+///A per-cell depth buffer that lives alongside a CharBuffer. Used by the Renderer to only draw a char when it is closer to the camera than whatever is already in that cell.
+/// Depth is measured along the camera's view direction, so smaller values are closer. Empty cells hold ```f32::INFINITY```.
+/// # Example
+/// ```
+/// # use ascii_renderer::prelude::*;
+/// let mut depth_buf = DepthBuffer::new(3, 3);
+/// assert!(depth_buf.test_and_set(1, 1, 5.0));
+/// assert!(!depth_buf.test_and_set(1, 1, 7.0)); //Further away, so it fails
+/// assert!(depth_buf.test_and_set(1, 1, 2.0));
+/// ```
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct DepthBuffer {
+    pub data: Vec<Vec<f32>>,
+    pub dimensions: (usize, usize),
+}
+
+impl DepthBuffer {
+    pub fn new(width: usize, height: usize) -> Self {
+        DepthBuffer {
+            data: vec![vec![f32::INFINITY; width]; height],
+            dimensions: (width, height),
+        }
+    }
+    pub fn get_depth(&self, x: usize, y: usize) -> Option<f32> {
+        self.data.get(y)?.get(x).copied()
+    }
+    pub fn test_and_set(&mut self, x: usize, y: usize, depth: f32) -> bool {
+        //! Writes the depth to the cell if it is closer than the depth already stored there. Returns whether the write happened, out of range cells always fail.
+        match self.data.get_mut(y).and_then(|row| row.get_mut(x)) {
+            Some(stored) if depth < *stored => {
+                *stored = depth;
+                true
+            }
+            _ => false,
+        }
+    }
+    pub fn clear(&mut self) {
+        //! Resets every cell to be infinitely far away
+        for row in self.data.iter_mut() {
+            for item in row.iter_mut() {
+                *item = f32::INFINITY;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn closer_depth_wins() {
+        let mut buf = DepthBuffer::new(2, 2);
+        assert!(buf.test_and_set(0, 1, 3.0));
+        assert!(!buf.test_and_set(0, 1, 3.0));
+        assert!(!buf.test_and_set(0, 1, 4.0));
+        assert!(buf.test_and_set(0, 1, 1.0));
+        assert_eq!(buf.get_depth(0, 1), Some(1.0));
+    }
+
+    #[test]
+    fn out_of_range_fails() {
+        let mut buf = DepthBuffer::new(2, 2);
+        assert!(!buf.test_and_set(2, 0, 1.0));
+        assert!(!buf.test_and_set(0, 2, 1.0));
+    }
+
+    #[test]
+    fn clearing() {
+        let mut buf = DepthBuffer::new(2, 2);
+        buf.test_and_set(1, 1, 1.0);
+        buf.clear();
+        assert_eq!(buf.get_depth(1, 1), Some(f32::INFINITY));
+    }
+}
@@ -0,0 +1,191 @@
+use super::rendering::{Mesh, Projection};
+use super::Vector3;
+use std::collections::HashMap;
+
+///Settings for drawing only the edges that outline a mesh's shape, rather than every one of its edges, which turns dense meshs (like scanned or CAD models) into a solid blob of chars. Set a Renderer's ```feature_edges``` to use them.
+/// The edges are taken from the outlines of the mesh's faces, and an edge is kept if it is:
+/// - a boundary, belonging to only one face, like the rim of an open surface.
+/// - a crease, where the faces on either side of it bend by more than ```crease_angle``` (in radians).
+/// - a silhouette, where one of the faces on either side of it faces the camera and the other faces away. Silhouettes move as the mesh or camera does, so they are found again every frame.
+///
+/// Edges that don't belong to any face and edges shared by more than two faces are always kept, and meshs without faces keep all of their edges.
+/// # Example
+/// ```
+/// # use ascii_renderer::prelude::*;
+/// let cube = ascii_renderer::primitives::cube(1.0);
+/// // * The diagonals splitting each side into triangles lie flat, so only the cube's 12 sides are left
+/// assert_eq!(cube.feature_edges(&FeatureEdges::default(), None).len(), 12);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeatureEdges {
+    pub boundaries: bool,
+    pub crease_angle: Option<f32>,
+    pub silhouettes: bool,
+}
+
+impl FeatureEdges {
+    pub fn new(boundaries: bool, crease_angle: Option<f32>, silhouettes: bool) -> Self {
+        Self {
+            boundaries,
+            crease_angle,
+            silhouettes,
+        }
+    }
+    /// Finds the feature edges of the mesh, where position gives each vertex's position. Silhouettes are only found if a projection is given, in which case the positions must be in view space.
+    pub(crate) fn find(
+        &self,
+        mesh: &Mesh,
+        position: impl Fn(usize) -> Option<Vector3>,
+        projection: Option<Projection>,
+    ) -> Vec<(usize, usize)> {
+        let faces = mesh.get_faces();
+        if faces.is_empty() {
+            return mesh.get_edges().clone();
+        }
+
+        // The normal and a point of each face next to each edge (with its ends in ascending order), along with whether the face goes along the edge in that order
+        let mut sides = HashMap::new();
+        let mut order = vec![];
+        for &(a, b, c) in faces.iter() {
+            let (Some(point_a), Some(point_b), Some(point_c)) =
+                (position(a), position(b), position(c))
+            else {
+                continue;
+            };
+            let normal = (point_b - point_a).cross(point_c - point_a);
+            if normal.len() == 0.0 {
+                continue; //Faces without any area don't point anywhere
+            }
+            for (from, to) in [(a, b), (b, c), (c, a)] {
+                sides
+                    .entry((from.min(to), from.max(to)))
+                    .or_insert_with(|| {
+                        order.push((from, to));
+                        vec![]
+                    })
+                    .push((normal, point_a, from < to));
+            }
+        }
+
+        let projection = projection.filter(|_| self.silhouettes);
+        let faces_camera = |normal: Vector3, point: Vector3| match projection {
+            Some(Projection::Orthographic { .. }) => normal.z < 0.0,
+            _ => normal.dot(point) < 0.0,
+        };
+        let mut edges: Vec<(usize, usize)> = order
+            .into_iter()
+            .filter(
+                |&(from, to)| match sides[&(from.min(to), from.max(to))][..] {
+                    [_] => self.boundaries,
+                    [(first, first_point, first_forward), (second, second_point, second_forward)] =>
+                    {
+                        // Faces wound the same way go along the edge they share in opposite directions, so if these don't, one is flipped to match the other
+                        let second = if first_forward == second_forward {
+                            -second
+                        } else {
+                            second
+                        };
+                        self.crease_angle
+                            .is_some_and(|angle| first.angle_between(second) > angle)
+                            || (projection.is_some()
+                                && faces_camera(first, first_point)
+                                    != faces_camera(second, second_point))
+                    }
+                    _ => true,
+                },
+            )
+            .collect();
+        edges.extend(
+            mesh.get_edges()
+                .iter()
+                .filter(|&&(from, to)| !sides.contains_key(&(from.min(to), from.max(to)))),
+        );
+        edges
+    }
+}
+
+impl std::default::Default for FeatureEdges {
+    fn default() -> Self {
+        //! Keeps boundaries, silhouettes and creases sharper than 30 degrees.
+        Self::new(true, Some(std::f32::consts::FRAC_PI_6), true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives;
+    use crate::rendering::Camera;
+    use crate::{vec2, vec3, Vector2};
+
+    #[test]
+    fn boundaries_and_creases() {
+        let edges_only = FeatureEdges::new(false, None, false);
+        let cube = primitives::cube(1.0);
+        assert!(cube.feature_edges(&edges_only, None).is_empty());
+        let creases = cube.feature_edges(&FeatureEdges::default(), None);
+        assert_eq!(creases.len(), 12);
+        let vertices = cube.get_verticies();
+        let side = vertices[&creases[0].0].distance(vertices[&creases[0].1]);
+        assert!(creases
+            .iter()
+            .all(|(a, b)| (vertices[a].distance(vertices[b]) - side).abs() < 1e-4));
+
+        // Only the rim of a flat grid is left
+        let grid = primitives::grid(vec2!(4.0, 4.0), (4, 4));
+        let rim = grid.feature_edges(&FeatureEdges::new(true, Some(0.1), false), None);
+        assert_eq!(rim.len(), 16);
+        assert!(rim.iter().all(|(a, b)| {
+            let (a, b) = (grid.get_verticies()[a], grid.get_verticies()[b]);
+            a.x.abs().max(a.z.abs()) == 2.0 && b.x.abs().max(b.z.abs()) == 2.0
+        }));
+
+        // Edges on no face are always kept, and faces wound either way give the same creases
+        let mut mesh = Mesh::default();
+        mesh.insert_vertices(vec![
+            (0, vec3!(0.0, 0.0, 0.0)),
+            (1, vec3!(1.0, 0.0, 0.0)),
+            (2, vec3!(1.0, 1.0, 0.0)),
+            (3, vec3!(0.0, 1.0, 0.0)),
+            (4, vec3!(5.0, 5.0, 5.0)),
+        ]);
+        mesh.add_faces(vec![(0, 1, 2), (0, 3, 2)]);
+        mesh.add_edge((4, 0));
+        let edges = mesh.feature_edges(&FeatureEdges::new(false, Some(0.1), false), None);
+        assert_eq!(edges, vec![(4, 0)]);
+        mesh.get_faces_mut().clear();
+        assert_eq!(mesh.feature_edges(&edges_only, None), vec![(4, 0)]);
+    }
+
+    #[test]
+    fn silhouettes() {
+        let outline = FeatureEdges::new(false, None, true);
+        let sphere = primitives::uv_sphere(1.0, 24, 12);
+        // Silhouettes need a camera
+        assert!(sphere.feature_edges(&outline, None).is_empty());
+
+        for camera in [
+            Camera::new(
+                vec3!(0.0, 0.0, -10.0),
+                vec3!(0.0, 0.0, 0.0),
+                vec2!(0.5, 0.5),
+            ),
+            Camera::new_orthographic(
+                vec3!(-10.0, 0.0, 0.0),
+                vec3!(0.0, 0.0, 0.0),
+                vec2!(3.0, 3.0),
+            ),
+        ] {
+            let mut camera = camera;
+            camera.look_at(vec3!(0.0, 0.0, 0.0), vec3!(0.0, -1.0, 0.0));
+            let edges = sphere.feature_edges(&outline, Some(&camera));
+            assert!(edges.len() >= 24);
+            // The outline is where the sphere's surface turns from facing the camera to facing away
+            let towards_camera = camera.position.normalize();
+            for (a, b) in edges {
+                let middle = (sphere.get_verticies()[&a] + sphere.get_verticies()[&b]) * 0.5;
+                assert!(middle.normalize().dot(towards_camera).abs() < 0.35);
+            }
+        }
+    }
+}
@@ -15,7 +15,7 @@ impl Logic for MyLogic {
 This implimentation defines that every single frame the program will do nothing and then continue, the most basic possible logic.
 
 Next, create a ```Runner```, pass an instance of your logic struct to it, and run it.
-```rust
+```rust,no_run
 use ascii_renderer::prelude::*;
 
 struct MyLogic;
@@ -39,8 +39,8 @@ The runner will proceed to run in a loop where it will run it's logic's ```proce
 
 The ```delta``` parameter of the process method is the amount of time (in seconds) that has passed since the last frame was drawn to the screen. It is necesary for non-frame-dependant movement.
 
-The ```CharBuffer``` can be mutated by changing individual chars (```set_char(&mut self, x, y, char)```), filling the entire buffer (```fill(&mut self, char)```), drawing lines (```draw_line(&mut self, line)```), or by rendering 3D graphics to it. The buffer is maintained between frames, you almost always should start ```process()``` with ```screen_buf.fill(' ');```.
-```rust
+The ```CharBuffer``` can be mutated by changing individual chars (```set_char(&mut self, x, y, char)```), filling the entire buffer (```fill(&mut self, char)```), drawing lines (```draw_line(&mut self, line)```, where the line's ```stroke``` is either a single char or ```Stroke::Slope``` to pick ```-```, ```|```, ```/``` and ```\``` from the slope of the line), or by rendering 3D graphics to it. The buffer is maintained between frames, you almost always should start ```process()``` with ```screen_buf.fill(' ');```.
+```rust,no_run
 use ascii_renderer::prelude::*;
 
 struct MyLogic;
@@ -49,14 +49,14 @@ impl Logic for MyLogic {
     fn process(&mut self, screen_buf: &mut CharBuffer, delta: f32) -> ProcessReturn {
         screen_buf.fill(' ');
 
-        let fps_string: String = (1.0 / delta).into();
-        let mut fps_chars = fps.chars();
+        let fps_string = (1.0 / delta).round().to_string();
+        let mut fps_chars = fps_string.chars();
 
-        screen_buf.set_char(0, 0, fps_chars.next().unwrap()).unwrap(); //Will write the fps to the screen
-        screen_buf.set_char(1, 0, fps_chars.next().unwrap()).unwrap();
+        screen_buf.set_char(0, 0, fps_chars.next().unwrap_or(' ')).unwrap(); //Will write the fps to the screen
+        screen_buf.set_char(1, 0, fps_chars.next().unwrap_or(' ')).unwrap();
 
         screen_buf.draw_line(Line {
-            char: '=',
+            stroke: Stroke::Char('='),
             points: (vec2!(0.0, 3.0), vec2!(5.0, 3.0)),
         }); //Will draw a line to the screen using '='
 
@@ -73,8 +73,8 @@ fn main() {
     ).run(true);    //true = clears the terminal between frames
 }
 ```
-To render 3D graphics to the ```CharBuffer```, we need to use a ```Renderer```. We don't want to instantiate a new ```Renderer``` every single frame, so we should store an instance of a ```Renderer``` wtihin a field of our logic struct. To draw graphics to the ```CharBuffer```, simply call ```draw()``` on the renderer, passing a mutable reference to the ```CharBuffer``` to it. In order to have something to render, you can create a 2x2x2 cube mesh using the ```create_cube()``` function and pass the cube to the renderer within it's declaration.
-```rust
+To render 3D graphics to the ```CharBuffer```, we need to use a ```Renderer```. We don't want to instantiate a new ```Renderer``` every single frame, so we should store an instance of a ```Renderer``` wtihin a field of our logic struct. To draw graphics to the ```CharBuffer```, simply call ```draw()``` on the renderer, passing a mutable reference to the ```CharBuffer``` to it. In order to have something to render, you can create a 2x2x2 cube mesh using the ```create_cube()``` function and pass the cube to the renderer within it's declaration. Spheres, cylinders, cones, tori, grids and more can be made the same way with the functions in the ```primitives``` module, e.g. ```primitives::uv_sphere(1.0, 16, 8)```. For showing data, the ```plot``` module turns a function ```f(x, y) -> z``` (or a parametric surface) into a mesh, and makes axes with ticks and labels to read it by. Labels are drawn from the renderer's ```labels```, on top of the meshs.
+```rust,no_run
 use ascii_renderer::prelude::*;
 
 struct MyLogic {
@@ -85,9 +85,6 @@ impl Logic for MyLogic {
     fn process(&mut self, screen_buf: &mut CharBuffer, delta: f32) -> ProcessReturn {
         screen_buf.fill(' ');
 
-        let fps_string: String = (1.0 / delta).into();
-        let mut fps_chars = fps.chars();
-
         self.renderer.draw(screen_buf);
 
         self.renderer.meshs[0].rotation.x += delta * 2.0;
@@ -103,20 +100,20 @@ fn main() {
         5, //Height
         25, //FPS Cap
         MyLogic {
-            renderer: Renderer {
-                meshs: vec![ascii_renderer::create_cube()],
-                camera: Camera {
-                    position: vec3!(0.0, 0.0, -7.0),
-                    rotation: vec3!(0.0, 0.0, 0.0),
-                    fov: vec2!(0.8, 0.8),   //Is in RADIANS. Make sure this is proportional to the dimensions of the CharBuffer, otherwise there will be stretching.
-                },
-            },
+            renderer: Renderer::new(
+                vec![ascii_renderer::create_cube()],
+                Camera::new(
+                    vec3!(0.0, 0.0, -7.0), //Position
+                    vec3!(0.0, 0.0, 0.0), //Rotation
+                    vec2!(0.8, 0.8), //FOV, is in RADIANS. Make sure this is proportional to the dimensions of the CharBuffer, otherwise there will be stretching.
+                ),
+            ),
         },
     ).run(true);    //true = clears the terminal between frames
 }
 ```
 For any values that need to be consistent, more fields can be added to the logic struct. For example, this logic contains a field that keeps track of how much time (in seconds) has passed since the runner started, and ```process()``` feeds that value into a sin function which determines the cube's scale in each dimension, creating a cool looking effect (as shown in [this](https://youtu.be/faViJzniUQA) video):
-```rust
+```rust,no_run
 use ascii_renderer::prelude::*;
 
 struct MyLogic {
@@ -149,26 +146,50 @@ fn main() {
         50,
         25,
         MyLogic {
-            renderer: Renderer {
-                meshs: vec![ascii_renderer::create_cube()],
-                camera: Camera {
-                    position: vec3!(0.0, 0.0, -7.0),
-                    rotation: vec3!(0.0, 0.0, 0.0),
-                    fov: vec2!(0.8, 0.8),
-                },
-            },
+            renderer: Renderer::new(
+                vec![ascii_renderer::create_cube()],
+                Camera::new(
+                    vec3!(0.0, 0.0, -7.0), //Position
+                    vec3!(0.0, 0.0, 0.0), //Rotation
+                    vec2!(0.8, 0.8), //FOV
+                ),
+            ),
             time_offset: 0.0,
         },
     );
     runner.run(true);
 }
 ```
-To load meshes from file (currently only .OBJ is supported), run the function ```AsciiObj::load(path)```, which will return a ```Result<AsciiObj, ObjError>```. After ```unwrap()```ing it, the ```AsciiObj``` can be converted into a ```Vec<Mesh>``` using ```into()```, which all together would look like ```let my_meshes: Vec<Mesh> = AsciiObj::load("face.obj").unwrap().into();```. 
+Wireframes can get hard to read once meshes overlap, since edges at the back get drawn on top of edges at the front. Setting ```depth_test``` to true on the renderer makes it keep a depth buffer while drawing, so only the closest edge is drawn in any given cell.
+
+Meshes can also carry triangle faces (```create_cube()``` comes with them), which lets the renderer fill them in instead of drawing edges. Setting the renderer's ```mode``` to ```RenderMode::Shaded(Shading::default())``` fills each face with a char picked from a brightness ramp (```".:-=+*#%@"``` by default, so even faces turned away from the light show up) depending on how directly the face points towards the shading's ```light_direction```. Shaded rendering is always depth tested, and meshes without faces are still drawn as wireframes. See ```examples/shaded_cube.rs``` for a spinning shaded cube.
+
+Everything that can be drawn to a ```CharBuffer``` can also be drawn to any other ```Canvas```. A ```BrailleCanvas``` packs 2 x 4 dots into every cell using Unicode braille patterns, giving a much higher resolution. Draw to it with the same ```draw_line()```, ```draw_lines()``` and ```Renderer::draw()``` calls, then copy it onto the screen buffer with ```copy_to()```, as shown in ```examples/braille_cube.rs```.
+
+For color, implement ```Logic<PixelBuffer>``` instead of ```Logic```, and the runner will hand the logic a ```PixelBuffer``` rather than a ```CharBuffer```. A ```PixelBuffer``` shows two pixels in every cell using the ```▀``` and ```▄``` half block chars, each with its own color. Give a mesh a color with ```mesh.color = Some(Color::Red)``` and the renderer will draw it in that color, while meshes without one are drawn in the buffer's ```pen``` color. See ```examples/pixel_cube.rs``` for two colored cubes.
+
+To keep the usual chars but add color, implement ```Logic<StyledBuffer>``` instead. Every cell of a ```StyledBuffer``` is a ```Cell``` holding a char and a ```Style```, which has an optional foreground color, an optional background color and crossterm ```Attributes``` (bold, dim, underlined, reverse and so on). Meshes are drawn with their color the same as on a ```PixelBuffer```, and text like a HUD label can be written with ```write_str(x, y, text, style)```. The runner only sends the escape sequences needed to change style from one cell to the next, and logic that doesn't need color can keep using the plain ```CharBuffer```, which sends none at all. See ```examples/styled_cube.rs```.
+
+To make something interactive, set the runner's ```capture_input``` to true before running it. The runner will then put the terminal in raw mode and, every frame before calling ```process()```, hand each waiting key press, key release (on terminals that report them), mouse click, drag, scroll and resize to the logic's ```event()``` method, which does nothing unless it is implemented. Pressing Ctrl-C ends the runner. See ```examples/interactive_cube.rs``` for a cube that can be spun with the arrow keys and dragged around with the mouse.
+
+While it runs, the runner keeps the terminal in a ```TerminalSession```, which hides the cursor, switches to the alternate screen when clearing the screen, and enables raw mode when capturing input. The terminal is put back the way it was when the runner ends, whether that's because ```process()``` returned ```ProcessReturn::End```, Ctrl-C was pressed or the logic panicked, so there is no need to ```reset``` the terminal afterwards. Without raw mode, Ctrl-C is caught by a handler the runner installs, unless the program already has one of its own, and pressing it twice kills the program after putting the terminal back. When stepping a runner by hand with ```step()```, start a ```TerminalSession``` yourself and keep it alive for as long as the frames are drawn.
 
-Often times meshes are far from the origin, causing the mesh to appear to spin in a large circle centered around the origin rather than rotate around a point when rotated. To fix this, you can run the ```recenter()``` method on the mesh before passing it to the renderer. ```recenter()``` returns the position the mesh was originally centered at, if you wish to maintain it's in-file position. 
+To fill the whole terminal, create the runner with ```Runner::new_auto_sized(fps_cap, logic)``` instead of ```Runner::new()```. Its buffer is sized to fit the terminal (a ```CharBuffer``` cell takes up two columns, since a space is printed after every char) and is resized, cleared and fully redrawn whenever the terminal changes size. To keep meshes from stretching as the buffer changes shape, set the camera's ```auto_aspect``` to true, which makes the renderer work the horizontal fov out from the vertical fov and the buffer's dimensions. Buffers can also be resized by hand with ```resize(width, height, clear)```, which keeps whatever still fits unless ```clear``` is true. See ```examples/fullscreen_cube.rs```.
+
+A camera made with ```Camera::new_auto_aspect(position, rotation, fov)``` takes a single (vertical) fov and has ```auto_aspect``` already set. The horizontal fov it works out also depends on its ```cell_aspect```, which is how wide each point of the buffer looks compared to how tall it is. The default of 1.0 suits a ```CharBuffer```, whose cells come out roughly square thanks to the space after every char. Setting a ```CharBuffer```'s ```padded``` to false leaves those spaces out, doubling how many cells fit across the terminal, and setting the camera's ```cell_aspect``` to 0.5 makes up for the cells then being about twice as tall as they are wide.
+
+Under the hood, every mesh is placed in the world with a single ```Matrix4``` (see ```Mesh::model_matrix()```), which combines its scale, rotation and position, and the camera supplies a view matrix and a perspective projection matrix. The same ```Matrix3```, ```Matrix4```, ```Quaternion``` and ```Transform``` types are available for your own maths. Adding to a mesh's ```rotation``` every frame can get stuck in gimbal lock, where two of the axes end up turning the mesh the same way. ```mesh.rotate_by(Quaternion::from_axis_angle(axis, angle))``` instead always turns the mesh around the given world axis, however it is already rotated.
+
+The camera uses a perspective projection by default, so further away things look smaller. For CAD-style or isometric views, where sizes don't change with distance, create the camera with ```Camera::new_orthographic(position, rotation, size)``` instead, where size is how much of the world (in world units) fits across and down the screen. Its ```projection``` can also be switched between ```Projection::Perspective``` and ```Projection::Orthographic { size }``` at any time, and ```auto_aspect``` works the same way for both.
+
+Rather than working out a camera's rotation by hand, ```camera.look_at(target, up)``` turns it to face a point. Keep in mind that the screen's y grows downwards, so with meshs that haven't been flipped, up is ```vec3!(0.0, -1.0, 0.0)```. For moving the camera with the keyboard and mouse, an ```OrbitController``` swings it around a target (drag to orbit, scroll to zoom) and a ```FlyController``` flies it around freely (WASD to move, drag to look). Both implement ```CameraController```: pass every event from your logic's ```event()``` method to the controller's ```event()```, call ```controller.update(&mut renderer.camera, delta)``` in ```process()``` before drawing, and set the runner's ```capture_input``` to true. The model viewer example puts this together with an OBJ file, or any OBJ or STL file passed to it (```cargo run --example model_viewer -- part.stl```).
+
+To load meshes from an OBJ file, run the function ```AsciiObj::load(path)```, which will return a ```Result<AsciiObj, ObjError>```. After ```unwrap()```ing it, the ```AsciiObj``` can be converted into a ```Vec<Mesh>``` using ```into()```, which all together would look like ```let my_meshes: Vec<Mesh> = AsciiObj::load("face.obj").unwrap().into();```. Every object in the file becomes its own mesh, with ```object``` set to its name and ```groups``` holding the name and faces of each of its groups (```mesh.get_group_faces(name)``` gets a group's faces), and the file's faces, normals and texture coordinates are kept, so loaded models can be drawn with ```RenderMode::Shaded``` too. Dense models can turn into a solid blob of chars as wireframes, since every edge of every triangle is drawn. Setting the renderer's ```feature_edges``` to ```Some(FeatureEdges::default())``` only draws the edges that outline the model's shape: the rims of open surfaces, creases sharper than 30 degrees, and the silhouette as seen from the camera. STL files, which CAD programs and 3D printing tools export, can be loaded with ```ascii_renderer::stl::load(path)```, which reads both ASCII and binary STL files straight into a single ```Mesh``` (or fails with an ```StlError```).
+
+Often times meshes are far from the origin, causing the mesh to appear to spin in a large circle centered around the origin rather than rotate around a point when rotated. To fix this, you can run the ```recenter()``` method on the mesh before passing it to the renderer. ```recenter()``` returns the position the mesh was originally centered at, if you wish to maintain it's in-file position.
 
 This example demonstrates overall how to load objs:
-```rust
+```rust,no_run
 use ascii_renderer::prelude::*;
 
 #[derive(Debug)]
@@ -201,38 +222,69 @@ fn main() {
         50,
         25,
         MyLogic {
-            renderer: Renderer {
-                meshs: my_meshes,
-                camera: Camera {
-                    position: vec3!(0.0, 0.0, -3.0),
-                    rotation: vec3!(0.0, 0.0, 0.0),
-                    fov: vec2!(0.8, 0.8),
-                },
-            },
+            renderer: Renderer::new(
+                my_meshes,
+                Camera::new(
+                    vec3!(0.0, 0.0, -3.0), //Position
+                    vec3!(0.0, 0.0, 0.0), //Rotation
+                    vec2!(0.8, 0.8), //FOV
+                ),
+            ),
         },
     );
     runner.run(true);
 }
 ```
+
+For things made of parts that move together, like the wheels of a car or a moon going around a planet, use the renderer's ```scene``` rather than its ```meshs```. A ```Scene``` is a tree of ```Node```s, each with a ```Transform``` relative to its parent and optionally a mesh, so moving or turning a node carries all of its children with it. ```scene.add(parent, node)``` returns a ```NodeId``` handle, which can be kept to change the node later with ```scene[id]```, and ```scene.find(name)``` looks a node up by its name. An ```AsciiObj``` can also be converted into a ```Scene``` with ```into()```, which gives every object in the file its own node named after it and holding its mesh. The solar system example shows a planet orbiting a sun with a moon orbiting the planet.
+
+Meshes can be saved back to OBJ files too, whether they were loaded or built in code. ```mesh.save_obj(path, bake_transform)``` saves a single mesh, ```AsciiObj::save_meshs(path, &renderer.meshs, bake_transforms)``` saves several as separate objects, and ```AsciiObj::save_scene(path, &renderer.scene)``` saves every visible mesh of a scene where it sits in the world. With the transforms baked in, each mesh's position, rotation and scale are applied to the vertices that get saved. Faces are saved as faces, and edges that aren't part of any face as lines, though lines can't be loaded back yet.
 */
 
+pub mod braille;
+pub mod canvas;
 pub mod char_buffer;
+pub mod controllers;
+pub mod depth_buffer;
+pub mod feature_edges;
 pub mod line;
 pub mod obj;
+pub mod pixel_buffer;
+pub mod plot;
+pub mod primitives;
 pub mod rendering;
 pub mod runner;
+pub mod scene;
+pub mod stl;
+pub mod styled_buffer;
+pub mod terminal;
+pub mod triangle;
 
 pub use rendering::{Vector2, Vector3};
 
 pub mod prelude {
+    pub use super::braille::BrailleCanvas;
+    pub use super::canvas::Canvas;
     pub use super::char_buffer::CharBuffer;
-    pub use super::line::Line;
+    pub use super::controllers::{CameraController, FlyController, OrbitController};
+    pub use super::depth_buffer::DepthBuffer;
+    pub use super::feature_edges::FeatureEdges;
+    pub use super::line::{Line, Stroke};
     pub use super::obj::AsciiObj;
     pub use super::obj::ObjError;
-    pub use super::rendering::{Camera, Mesh, Renderer};
+    pub use super::pixel_buffer::PixelBuffer;
+    pub use super::rendering::{Camera, Label, Mesh, Projection, RenderMode, Renderer, Shading};
+    pub use super::rendering::{Matrix3, Matrix4, Quaternion, Transform};
     pub use super::runner::ProcessReturn;
-    pub use super::runner::{Logic, Runner};
+    pub use super::runner::{Logic, Runner, Screen};
+    pub use super::scene::{Node, NodeId, Scene};
+    pub use super::stl::StlError;
+    pub use super::styled_buffer::{Cell, Style, StyledBuffer};
+    pub use super::terminal::TerminalSession;
+    pub use super::triangle::Triangle;
     pub use super::{vec2, vec3, Vector2, Vector3};
+    pub use crossterm::event::{Event, KeyCode, KeyEventKind, MouseButton, MouseEventKind};
+    pub use crossterm::style::{Attribute, Attributes, Color};
 }
 
 pub fn create_cube() -> rendering::Mesh {
@@ -267,5 +319,21 @@ pub fn create_cube() -> rendering::Mesh {
     cube.add_edge((2, 6));
     cube.add_edge((3, 7));
 
+    //Faces, two triangles per side
+    cube.add_faces(vec![
+        (0, 1, 2),
+        (0, 2, 3),
+        (4, 6, 5),
+        (4, 7, 6),
+        (0, 5, 1),
+        (0, 4, 5),
+        (3, 2, 6),
+        (3, 6, 7),
+        (0, 3, 7),
+        (0, 7, 4),
+        (1, 5, 6),
+        (1, 6, 2),
+    ]);
+
     cube
 }
@@ -1,139 +1,240 @@
-use super::char_buffer::CharBuffer;
-use super::Vector2;
+use super::canvas::Canvas;
+use super::depth_buffer::DepthBuffer;
+use super::{vec2, Vector2};
 
 /// The struct fed to a CharBuffer for drawing lines.
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
 pub struct Line {
-    pub char: char,
+    pub stroke: Stroke,
     pub points: (Vector2, Vector2),
 }
 
-impl Into<((usize, usize), (usize, usize))> for Line {
-    fn into(self) -> ((usize, usize), (usize, usize)) {
-        #[inline]
-        fn f32_to_usize(value: f32) -> usize {
-            value.round() as usize
-        }
+/// Determines which char is used for each cell of a line.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub enum Stroke {
+    /// Every cell of the line uses the same char.
+    Char(char),
+    /// Each cell's char is picked from the slope of the line (```-```, ```_```, ```|```, ```/``` or ```\```), and where a line crosses or meets another sloped line the cell becomes a join (```+``` or ```X```). Gives wireframes a hand drawn look.
+    Slope,
+}
 
-        if (self.points.0.x < 0.0 && self.points.1.x < 0.0)
-            || (self.points.0.y < 0.0 && self.points.1.y < 0.0)
-        {
-            //line is entirely offscreen, the coords are set near the 32-bit unsigned int limit
-            return (
-                (4_000_000_000, 4_000_000_000),
-                (4_000_000_000, 4_000_000_000),
-            );
+impl Stroke {
+    /// Picks the glyph for a cell of a sloped line going in the given direction. ```offset``` is how far below the center of the cell the line passes, from -0.5 to 0.5, which is used to pick between ```-``` and ```_```.
+    pub fn slope_glyph(direction: Vector2, offset: f32) -> char {
+        // tan(22.5°) and tan(67.5°), splitting the directions into 8 equal slices
+        let (shallow, steep) = (0.414_213_57, 2.414_213_6);
+        let (dx, dy) = (direction.x.abs(), direction.y.abs());
+        if dy <= dx * shallow {
+            if offset > 0.25 {
+                '_'
+            } else {
+                '-'
+            }
+        } else if dy >= dx * steep {
+            '|'
+        } else if (direction.x > 0.0) == (direction.y > 0.0) {
+            '\\' //Y increases downwards, so this is going down and to the right
+        } else {
+            '/'
         }
-        if self.points.0.x == self.points.1.x || self.points.0.y == self.points.1.y {
-            //Vertical lines and Horizontal lines - converting direcly to usize is valid as it will just shift any offscreen endpoint vertically/horizontally until they are 0
-            return (
-                (f32_to_usize(self.points.0.x), f32_to_usize(self.points.0.y)),
-                (f32_to_usize(self.points.1.x), f32_to_usize(self.points.1.y)),
-            );
+    }
+    /// Picks the char for a cell where a slope glyph is being drawn over the existing char. Crossing horizontal and vertical glyphs become ```+```, crossing diagonals become ```X```, and anything that isn't a slope glyph is simply overwritten.
+    pub fn join_glyph(existing: char, new: char) -> char {
+        let class = |glyph: char| match glyph {
+            '-' | '_' => Some(0),
+            '|' => Some(1),
+            '/' => Some(2),
+            '\\' => Some(3),
+            '+' | 'X' => Some(4),
+            _ => None,
+        };
+        match (class(existing), class(new)) {
+            (Some(existing_class), Some(new_class)) if existing_class == new_class => new,
+            (Some(4), Some(_)) => existing,
+            (Some(2 | 3), Some(2 | 3)) => 'X',
+            (Some(_), Some(_)) => '+',
+            _ => new,
         }
+    }
+}
 
-        let first = if self.points.0.x < self.points.1.x {
-            self.points.0
-        } else {
-            self.points.1
-        }; //Handles if the second point comes before the first.
-        let second = if self.points.0.x < self.points.1.x {
-            self.points.1
-        } else {
-            self.points.0
-        };
+impl std::convert::From<char> for Stroke {
+    fn from(char: char) -> Self {
+        Stroke::Char(char)
+    }
+}
 
-        let slope = (second.y - first.y) / (second.x - first.x);
-        let equation_for_y = |x: f32| slope * (x - first.x) + first.y;
-        let equation_for_x = |y: f32| (y - first.y) / slope + first.x;
+impl Line {
+    /// Clips the line to a buffer with the given dimensions using the Liang–Barsky algorithm. Returns the range of the line's parameter (0.0 at the first point, 1.0 at the second) that is on the buffer, or None if none of it is.
+    /// A cell covers everything that rounds to it, so the clipping rectangle extends half a cell past the outer cells' centers.
+    pub fn clip_range(&self, dimensions: (usize, usize)) -> Option<(f32, f32)> {
+        if dimensions.0 == 0 || dimensions.1 == 0 {
+            return None;
+        }
+        let (start, end) = self.points;
+        let delta = end - start;
+        let (min, max) = (
+            vec2!(-0.5, -0.5),
+            vec2!(dimensions.0 as f32 - 0.5, dimensions.1 as f32 - 0.5),
+        );
 
-        if first.x < 0.0 {
-            //If the first point is offscreen to the left
-            let ret = ((0.0, equation_for_y(0.0)), (second.x, second.y));
-            if ret.0 .1 < 0.0 {
-                return (
-                    (f32_to_usize(equation_for_x(0.0)), 0),
-                    (f32_to_usize(ret.1 .0), f32_to_usize(ret.1 .1)),
-                );
+        let (mut t0, mut t1) = (0.0_f32, 1.0_f32);
+        // Each boundary as (p, q), where the line is inside that boundary wherever p * t <= q
+        for (p, q) in [
+            (-delta.x, start.x - min.x),
+            (delta.x, max.x - start.x),
+            (-delta.y, start.y - min.y),
+            (delta.y, max.y - start.y),
+        ] {
+            if p == 0.0 {
+                if q < 0.0 {
+                    return None; //Parallel to the boundary and outside of it
+                }
+                continue;
             }
-            return (
-                (f32_to_usize(ret.0 .0), f32_to_usize(ret.0 .1)),
-                (f32_to_usize(ret.1 .0), f32_to_usize(ret.1 .1)),
-            );
-        }
-        if first.y < 0.0 {
-            //If the first point is above the screen
-            let ret = ((equation_for_x(0.0), 0.0), (second.x, second.y));
-            if ret.0 .0 < 0.0 {
-                return {
-                    (
-                        (0, f32_to_usize(equation_for_y(0.0))),
-                        (f32_to_usize(ret.1 .0), f32_to_usize(ret.1 .1)),
-                    )
-                };
+            let t = q / p;
+            if p < 0.0 {
+                t0 = t0.max(t);
+            } else {
+                t1 = t1.min(t);
             }
-            return (
-                (f32_to_usize(ret.0 .0), f32_to_usize(ret.0 .1)),
-                (f32_to_usize(ret.1 .0), f32_to_usize(ret.1 .1)),
-            );
-        } else if second.y < 0.0 {
-            //If the second point is above the screen
-            return (
-                (f32_to_usize(first.x), f32_to_usize(first.y)),
-                (f32_to_usize(equation_for_x(0.0)), 0),
-            );
-        } // We don't need to worry about if the second point is left of the screen, as we know that the first point is the leftmost one and if they are both offscreen to the left then there was an early return
-
-        (
-            (f32_to_usize(self.points.0.x), f32_to_usize(self.points.0.y)),
-            (f32_to_usize(self.points.1.x), f32_to_usize(self.points.1.y)),
-        )
+            if t0 > t1 {
+                return None;
+            }
+        }
+        if t0.is_nan() || t1.is_nan() {
+            return None;
+        }
+        Some((t0, t1))
+    }
+    /// Clips the line to a buffer with the given dimensions, returning the part of it that is on the buffer.
+    pub fn clip(&self, dimensions: (usize, usize)) -> Option<Line> {
+        let (t0, t1) = self.clip_range(dimensions)?;
+        let delta = self.points.1 - self.points.0;
+        // Clamping keeps float error from far away ends from pushing the clipped ends back off the buffer
+        let clamp = |point: Vector2| {
+            vec2!(
+                point.x.clamp(-0.5, dimensions.0 as f32 - 0.5),
+                point.y.clamp(-0.5, dimensions.1 as f32 - 0.5)
+            )
+        };
+        Some(Line {
+            stroke: self.stroke,
+            points: (
+                clamp(self.points.0 + delta * t0),
+                clamp(self.points.0 + delta * t1),
+            ),
+        })
+    }
+    /// Clips the line to a buffer with the given dimensions and converts its ends to the coords of the cells they land in. Both sets of coords are always within the buffer.
+    pub fn to_cells(&self, dimensions: (usize, usize)) -> Option<((usize, usize), (usize, usize))> {
+        let clipped = self.clip(dimensions)?;
+        let to_cell = |point: Vector2| {
+            (
+                (point.x.round().max(0.0) as usize).min(dimensions.0 - 1),
+                (point.y.round().max(0.0) as usize).min(dimensions.1 - 1),
+            )
+        };
+        Some((to_cell(clipped.points.0), to_cell(clipped.points.1)))
     }
 }
 
-impl CharBuffer {
-    pub fn draw_line(&mut self, line: Line) {
-        //! Draws an individual line to the buffer
-        let coords: ((usize, usize), (usize, usize)) = line.into();
-        draw_line(line.char, self, coords.0, coords.1);
+pub(crate) fn draw_line<C: Canvas + ?Sized>(canvas: &mut C, line: Line) {
+    //! Draws an individual line to the canvas. Whatever part of the line is off the canvas is clipped away.
+    match line.stroke {
+        Stroke::Char(char) => {
+            if let Some(coords) = line.to_cells(canvas.dimensions()) {
+                draw_cells(char, canvas, coords.0, coords.1);
+            }
+        }
+        Stroke::Slope => {
+            let direction = line.points.1 - line.points.0;
+            trace_line(line, canvas.dimensions(), |(x, y), point, _| {
+                plot_slope_glyph(canvas, x, y, direction, point.y - y as f32);
+            });
+        }
     }
-    pub fn draw_lines(&mut self, lines: Vec<Line>) {
-        //! Draws lines to the buffer. The first lines in the vector will be drawn first.
-        //! # Example
-        //! ```
-        //! let mut buf = CharBuffer::new(10, 10);
-        //! let lines = vec![
-        //!     Line {
-        //!         char: '+',
-        //!         points: (vec2!(2.0, 2.0), vec2!(8.0, 8.0)),
-        //!     },
-        //!     Line {
-        //!         char: '=',
-        //!         points: (vec2!(2.0, 8.0), vec2!(8.0, 2.0)),
-        //!     },
-        //! ];
-        //! buf.draw_lines(lines);
-        //! println!("{buf}");
-        //! ```
-        for line in lines {
-            self.draw_line(line);
+}
+
+pub(crate) fn draw_line_depth_tested<C: Canvas + ?Sized>(
+    canvas: &mut C,
+    line: Line,
+    depths: (f32, f32),
+    depth_buffer: &mut DepthBuffer,
+) {
+    //! Draws a line where each end has a depth, which is linearly interpolated along the line. A char is only written to a cell if it is closer than the depth already in the depth buffer.
+    let direction = line.points.1 - line.points.0;
+    trace_line(line, canvas.dimensions(), |(x, y), point, t| {
+        let depth = depths.0 + (depths.1 - depths.0) * t;
+        if depth_buffer.test_and_set(x, y, depth) {
+            match line.stroke {
+                Stroke::Char(char) => canvas.plot(x, y, char),
+                Stroke::Slope => plot_slope_glyph(canvas, x, y, direction, point.y - y as f32),
+            }
         }
+    });
+}
+
+fn plot_slope_glyph<C: Canvas + ?Sized>(
+    canvas: &mut C,
+    x: usize,
+    y: usize,
+    direction: Vector2,
+    offset: f32,
+) {
+    let glyph = Stroke::slope_glyph(direction, offset);
+    match canvas.char_at(x, y) {
+        Some(existing) => canvas.plot(x, y, Stroke::join_glyph(existing, glyph)),
+        None => canvas.plot(x, y, glyph),
     }
 }
 
-fn draw_line(
+fn trace_line(
+    line: Line,
+    dimensions: (usize, usize),
+    mut f: impl FnMut((usize, usize), Vector2, f32),
+) {
+    //! Steps along the part of the line that is on a buffer of the given dimensions, one cell at a time, calling f with the coords of the cell, the exact point on the line, and the line's parameter (0.0 at the first point and 1.0 at the second) at that point.
+    let Some((t0, t1)) = line.clip_range(dimensions) else {
+        return;
+    };
+    let delta = line.points.1 - line.points.0;
+    let clipped_delta = delta * (t1 - t0);
+    let steps = clipped_delta
+        .x
+        .abs()
+        .max(clipped_delta.y.abs())
+        .ceil()
+        .max(1.0) as usize;
+
+    for step in 0..=steps {
+        let t = t0 + (t1 - t0) * (step as f32 / steps as f32);
+        let point = line.points.0 + delta * t;
+        let cell = (
+            (point.x.round().max(0.0) as usize).min(dimensions.0 - 1),
+            (point.y.round().max(0.0) as usize).min(dimensions.1 - 1),
+        );
+        f(cell, point, t);
+    }
+}
+
+fn draw_cells<C: Canvas + ?Sized>(
     char: char,
-    buf: &mut CharBuffer,
+    buf: &mut C,
     mut start_coords: (usize, usize),
     mut end_coords: (usize, usize),
 ) {
     //! The lower level function for drawing lines. Works, but its best to use higher level as it eliviates the jank of the usize params
 
+    if start_coords == end_coords {
+        //Single cell lines would otherwise have a NaN slope
+        buf.plot(start_coords.0, start_coords.1, char);
+        return;
+    }
+
     if start_coords.0 > end_coords.0 {
         //Eliminates left-pointing lines
-        let b = start_coords;
-        start_coords = end_coords;
-        end_coords = b;
+        std::mem::swap(&mut start_coords, &mut end_coords);
     }
 
     let slope = (end_coords.1 as f32 - start_coords.1 as f32)
@@ -151,9 +252,9 @@ fn draw_line(
     }
 }
 
-fn draw_vertical(
+fn draw_vertical<C: Canvas + ?Sized>(
     char: char,
-    buf: &mut CharBuffer,
+    buf: &mut C,
     start_coords: (usize, usize),
     end_coords: (usize, usize),
     is_up: bool,
@@ -165,20 +266,20 @@ fn draw_vertical(
         let equation =
             |y: usize| (inv_slope * (y - start_coords.1) as f32 + start_coords.0 as f32) as usize;
         for y in start_coords.1..=end_coords.1 {
-            buf.set_char(equation(y), y, char);
+            buf.plot(equation(y), y, char);
         }
     } else {
         let equation =
             |y: usize| (inv_slope * (y - end_coords.1) as f32 + end_coords.0 as f32) as usize;
         for y in end_coords.1..=start_coords.1 {
-            buf.set_char(equation(y), y, char);
+            buf.plot(equation(y), y, char);
         }
     }
 }
 
-fn draw_horizontal(
+fn draw_horizontal<C: Canvas + ?Sized>(
     char: char,
-    buf: &mut CharBuffer,
+    buf: &mut C,
     start_coords: (usize, usize),
     end_coords: (usize, usize),
 ) {
@@ -189,95 +290,185 @@ fn draw_horizontal(
         |x: usize| (slope * (x - start_coords.0) as f32 + start_coords.1 as f32) as usize;
 
     for x in start_coords.0..=end_coords.0 {
-        buf.set_char(x, equation(x), char);
+        buf.plot(x, equation(x), char);
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::char_buffer::CharBuffer;
+    fn cells(a: Vector2, b: Vector2) -> Option<((usize, usize), (usize, usize))> {
+        Line {
+            stroke: Stroke::Char('x'),
+            points: (a, b),
+        }
+        .to_cells((10, 10))
+    }
+
     #[test]
-    fn line_conversion() {
-        let line = Line {
-            char: 'x',
-            points: (vec2!(-1.0, 5.0), vec2!(3.0, 4.0)),
-        };
+    fn line_clipping_inside() {
         assert_eq!(
-            Into::<((usize, usize), (usize, usize))>::into(line),
-            ((0, 5), (3, 4))
+            cells(vec2!(1.3, 2.5), vec2!(4.3, 3.9)),
+            Some(((1, 3), (4, 4)))
         );
-        let line = Line {
-            char: 'x',
-            points: (vec2!(3.0, 4.0), vec2!(1.0, -2.0)),
-        };
         assert_eq!(
-            Into::<((usize, usize), (usize, usize))>::into(line),
-            ((2, 0), (3, 4))
+            cells(vec2!(9.0, 9.0), vec2!(0.0, 0.0)),
+            Some(((9, 9), (0, 0)))
         );
-        let line = Line {
-            char: 'x',
-            points: (vec2!(1.0, 5.0), vec2!(3.0, -1.0)),
-        };
+    }
+
+    #[test]
+    fn line_clipping_single_edges() {
+        //Left
         assert_eq!(
-            Into::<((usize, usize), (usize, usize))>::into(line),
-            ((1, 5), (3, 0))
+            cells(vec2!(-1.0, 5.0), vec2!(3.0, 4.0)),
+            Some(((0, 5), (3, 4)))
         );
-        let line = Line {
-            char: 'x',
-            points: (vec2!(-1.0, 5.0), vec2!(3.0, 5.0)),
-        };
+        //Top
         assert_eq!(
-            Into::<((usize, usize), (usize, usize))>::into(line),
-            ((0, 5), (3, 5))
+            cells(vec2!(3.0, 4.0), vec2!(1.0, -2.0)),
+            Some(((3, 4), (2, 0)))
         );
-        let line = Line {
-            char: 'x',
-            points: (vec2!(1.0, -2.0), vec2!(1.0, 3.0)),
-        };
+        //Right
         assert_eq!(
-            Into::<((usize, usize), (usize, usize))>::into(line),
-            ((1, 0), (1, 3))
+            cells(vec2!(5.0, 5.0), vec2!(15.0, 0.0)),
+            Some(((5, 5), (9, 3)))
         );
-        let line = Line {
-            char: 'x',
-            points: (vec2!(1.0, -2.0), vec2!(1.0, -3.0)),
-        };
+        //Bottom
         assert_eq!(
-            Into::<((usize, usize), (usize, usize))>::into(line),
-            (
-                (4_000_000_000, 4_000_000_000),
-                (4_000_000_000, 4_000_000_000)
-            )
+            cells(vec2!(2.0, 5.0), vec2!(2.0, 25.0)),
+            Some(((2, 5), (2, 9)))
         );
-        let line = Line {
-            char: 'x',
-            points: (vec2!(-1.0, 2.0), vec2!(-4.0, 3.0)),
-        };
+        //Horizontal and vertical lines crossing the whole buffer
         assert_eq!(
-            Into::<((usize, usize), (usize, usize))>::into(line),
-            (
-                (4_000_000_000, 4_000_000_000),
-                (4_000_000_000, 4_000_000_000)
-            )
+            cells(vec2!(-5.0, 5.0), vec2!(100.0, 5.0)),
+            Some(((0, 5), (9, 5)))
         );
-        let line = Line {
-            char: 'x',
-            points: (vec2!(-1.0, 2.0), vec2!(-4.0, -3.0)),
-        };
         assert_eq!(
-            Into::<((usize, usize), (usize, usize))>::into(line),
-            (
-                (4_000_000_000, 4_000_000_000),
-                (4_000_000_000, 4_000_000_000)
-            )
+            cells(vec2!(1.0, -2.0), vec2!(1.0, 3.0)),
+            Some(((1, 0), (1, 3)))
         );
-        let line = Line {
-            char: 'x',
-            points: (vec2!(1.3, 2.5), vec2!(4.3, 3.9)),
-        };
+    }
+
+    #[test]
+    fn line_clipping_corners() {
+        //Diagonals through every pair of opposite corners, starting far outside
+        assert_eq!(
+            cells(vec2!(-10.0, -10.0), vec2!(20.0, 20.0)),
+            Some(((0, 0), (9, 9)))
+        );
+        assert_eq!(
+            cells(vec2!(19.0, -10.0), vec2!(-10.0, 19.0)),
+            Some(((9, 0), (0, 9)))
+        );
+        assert_eq!(
+            cells(vec2!(20.0, 20.0), vec2!(-10.0, -10.0)),
+            Some(((9, 9), (0, 0)))
+        );
+        assert_eq!(
+            cells(vec2!(-10.0, 19.0), vec2!(19.0, -10.0)),
+            Some(((0, 9), (9, 0)))
+        );
+    }
+
+    #[test]
+    fn line_clipping_offscreen() {
+        //Entirely within each of the eight regions around the buffer
+        for (a, b) in [
+            (vec2!(-1.0, 2.0), vec2!(-4.0, 3.0)),
+            (vec2!(1.0, -2.0), vec2!(1.0, -3.0)),
+            (vec2!(12.0, 2.0), vec2!(15.0, 8.0)),
+            (vec2!(2.0, 12.0), vec2!(8.0, 1000.0)),
+            (vec2!(-1.0, -2.0), vec2!(-4.0, -3.0)),
+            (vec2!(11.0, -2.0), vec2!(14.0, -3.0)),
+            (vec2!(11.0, 12.0), vec2!(14.0, 13.0)),
+            (vec2!(-1.0, 12.0), vec2!(-4.0, 13.0)),
+        ] {
+            assert_eq!(cells(a, b), None);
+        }
+        //Crosses two regions but misses the corner of the buffer
+        assert_eq!(cells(vec2!(-5.0, 3.0), vec2!(3.0, -5.0)), None);
+        assert_eq!(cells(vec2!(8.0, 15.0), vec2!(15.0, 8.0)), None);
+        //Off the buffer on both sides of the same axis, but still crossing it
         assert_eq!(
-            Into::<((usize, usize), (usize, usize))>::into(line),
-            ((1, 3), (4, 4))
+            cells(vec2!(4.0, -100.0), vec2!(5.0, 100.0)),
+            Some(((4, 0), (5, 9)))
         );
     }
+
+    #[test]
+    fn drawing_far_offscreen_lines() {
+        let mut buf = CharBuffer::new(3, 3);
+        buf.draw_line(Line {
+            stroke: Stroke::Char('x'),
+            points: (vec2!(-1e6, 1.0), vec2!(1e6, 1.0)),
+        });
+        buf.draw_line(Line {
+            stroke: Stroke::Char('x'),
+            points: (vec2!(5e9, 5e9), vec2!(6e9, -6e9)),
+        });
+        buf.draw_line(Line {
+            stroke: Stroke::Char('o'),
+            points: (vec2!(2.0, 2.0), vec2!(2.0, 2.0)),
+        });
+        assert_eq!(&buf.to_string(), "      \nx x x \n    o \n");
+    }
+
+    #[test]
+    fn slope_glyphs() {
+        let mut buf = CharBuffer::new(5, 5);
+        let lines = [
+            (vec2!(0.0, 0.0), vec2!(4.0, 0.0)),
+            (vec2!(0.0, 0.0), vec2!(0.0, 4.0)),
+            (vec2!(0.0, 0.0), vec2!(4.0, 4.0)),
+            (vec2!(0.0, 4.0), vec2!(4.0, 0.0)),
+            (vec2!(0.0, 4.3), vec2!(4.0, 4.3)),
+        ];
+        for points in lines {
+            buf.draw_line(Line {
+                stroke: Stroke::Slope,
+                points,
+            });
+        }
+        assert_eq!(
+            &buf.to_string(),
+            "+ - - - + \n| \\   /   \n|   X     \n| /   \\   \n+ _ _ _ + \n"
+        );
+    }
+
+    #[test]
+    fn join_glyphs() {
+        assert_eq!(Stroke::join_glyph(' ', '|'), '|');
+        assert_eq!(Stroke::join_glyph('a', '/'), '/');
+        assert_eq!(Stroke::join_glyph('-', '_'), '_');
+        assert_eq!(Stroke::join_glyph('-', '|'), '+');
+        assert_eq!(Stroke::join_glyph('/', '-'), '+');
+        assert_eq!(Stroke::join_glyph('/', '\\'), 'X');
+        assert_eq!(Stroke::join_glyph('X', '|'), 'X');
+    }
+
+    #[test]
+    fn depth_tested_lines() {
+        let mut buf = CharBuffer::new(5, 5);
+        let mut depth_buf = DepthBuffer::new(5, 5);
+        let near = Line {
+            stroke: Stroke::Char('n'),
+            points: (vec2!(0.0, 2.0), vec2!(4.0, 2.0)),
+        };
+        let far = Line {
+            stroke: Stroke::Char('f'),
+            points: (vec2!(2.0, 0.0), vec2!(2.0, 4.0)),
+        };
+        buf.draw_line_depth_tested(near, (1.0, 1.0), &mut depth_buf);
+        buf.draw_line_depth_tested(far, (5.0, 5.0), &mut depth_buf);
+        assert_eq!(buf.get_char(2, 2), Some('n'));
+        assert_eq!(buf.get_char(2, 0), Some('f'));
+
+        buf.fill(' ');
+        depth_buf.clear();
+        buf.draw_line_depth_tested(far, (5.0, 5.0), &mut depth_buf);
+        buf.draw_line_depth_tested(near, (1.0, 1.0), &mut depth_buf);
+        assert_eq!(buf.get_char(2, 2), Some('n'));
+    }
 }
@@ -1,7 +1,9 @@
 use super::prelude::*;
+use obj::Obj;
 pub use obj::ObjError;
-use obj::{Obj, Object};
-use std::{collections::HashMap, hash::Hash, ops::Deref};
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufWriter, Write};
 
 #[derive(Debug, Clone)]
 pub struct AsciiObj(Obj);
@@ -11,81 +13,557 @@ impl AsciiObj {
         self.0.save(path)
     }
     pub fn load(path: impl AsRef<std::path::Path>) -> Result<Self, ObjError> {
-        Obj::load(path).map(|x| AsciiObj(x))
+        Obj::load(path).map(AsciiObj)
+    }
+    pub fn save_meshs(
+        path: impl AsRef<std::path::Path>,
+        meshs: &[Mesh],
+        bake_transforms: bool,
+    ) -> Result<(), ObjError> {
+        //! Saves meshs made or edited in code (such as a renderer's ```meshs```) to an OBJ file, overwriting it if it already exists. See ```write_meshs()```.
+        let mut out = BufWriter::new(File::create(path)?);
+        Self::write_meshs(&mut out, meshs, bake_transforms)?;
+        Ok(out.flush()?)
+    }
+    pub fn write_meshs(
+        out: &mut impl Write,
+        meshs: &[Mesh],
+        bake_transforms: bool,
+    ) -> Result<(), ObjError> {
+        //! Writes meshs in the OBJ format. Each mesh becomes an object named after the mesh's ```object``` (or "mesh_1", "mesh_2" and so on for meshs without one), with its faces split into the mesh's ```groups```, so that files loaded with ```load()``` are saved the way they were.
+        //! Faces are written along with any normals and UVs their vertices have, and edges that aren't part of a face (all of them, for meshs without faces) are written as lines. Note that ```load()``` skips lines, so meshs without faces can be saved but not loaded back.
+        //! If bake_transforms is true, each mesh's position, rotation and scale are applied to its vertices and normals. Otherwise the vertices are written as they are stored, and the transforms are lost.
+        let parts = meshs.iter().enumerate().map(|(i, mesh)| {
+            let name = mesh
+                .object
+                .clone()
+                .unwrap_or_else(|| format!("mesh_{}", i + 1));
+            (mesh, bake_transforms.then(|| mesh.model_matrix()), name)
+        });
+        write_parts(out, parts)
+    }
+    pub fn save_scene(path: impl AsRef<std::path::Path>, scene: &Scene) -> Result<(), ObjError> {
+        //! Saves every visible mesh of a scene to an OBJ file, overwriting it if it already exists. See ```write_scene()```.
+        let mut out = BufWriter::new(File::create(path)?);
+        Self::write_scene(&mut out, scene)?;
+        Ok(out.flush()?)
+    }
+    pub fn write_scene(out: &mut impl Write, scene: &Scene) -> Result<(), ObjError> {
+        //! Writes every visible mesh of a scene in the OBJ format, as ```write_meshs()``` does. OBJ files have no way of storing the tree of nodes, so the meshs are written where they are in the world, and meshs without an ```object``` are named after their node.
+        let parts = scene.mesh_nodes().into_iter().map(|(node, matrix)| {
+            let mesh = node.mesh.as_ref().unwrap();
+            let name = mesh.object.clone().unwrap_or_else(|| node.name.clone());
+            (mesh, Some(matrix * mesh.model_matrix()), name)
+        });
+        write_parts(out, parts)
     }
 }
 
-impl std::convert::Into<Vec<Mesh>> for AsciiObj {
-    fn into(self) -> Vec<Mesh> {
-        #[inline]
-        fn simple_polygon_to_edges(polygon: obj::SimplePolygon) -> Vec<(usize, usize)> {
-            let last_vertex_index = polygon.0.last().unwrap().0;
-            polygon
-                .0
+impl Mesh {
+    pub fn save_obj(
+        &self,
+        path: impl AsRef<std::path::Path>,
+        bake_transform: bool,
+    ) -> Result<(), ObjError> {
+        //! Saves the mesh on its own to an OBJ file, overwriting it if it already exists. See ```AsciiObj::write_meshs()```.
+        AsciiObj::save_meshs(path, std::slice::from_ref(self), bake_transform)
+    }
+}
+
+/// Writes each mesh as an object with the given name, with its vertices moved by the matrix if there is one.
+fn write_parts<'a>(
+    out: &mut impl Write,
+    parts: impl Iterator<Item = (&'a Mesh, Option<Matrix4>, String)>,
+) -> Result<(), ObjError> {
+    writeln!(out, "# Exported by ascii_renderer")?;
+    // How many positions, UVs and normals have been written, since OBJ indices count from the start of the file
+    let (mut position_count, mut uv_count, mut normal_count) = (0, 0, 0);
+    for (mesh, matrix, name) in parts {
+        writeln!(out, "o {name}")?;
+
+        let (normal_matrix, mirrored) = match &matrix {
+            Some(matrix) => normal_matrix(matrix),
+            None => (Matrix3::IDENTITY, false),
+        };
+        let mut indices: Vec<usize> = mesh.get_verticies().keys().copied().collect();
+        indices.sort_unstable();
+        // Vertex index in the mesh -> (position, UV and normal index in the file)
+        let mut written: HashMap<usize, (usize, Option<usize>, Option<usize>)> = HashMap::new();
+        for &index in indices.iter() {
+            let mut position = mesh.get_verticies()[&index];
+            if let Some(matrix) = &matrix {
+                position = matrix.transform_point(position);
+            }
+            writeln!(out, "v {} {} {}", position.x, position.y, position.z)?;
+            position_count += 1;
+            written.insert(index, (position_count, None, None));
+        }
+        for &index in indices.iter() {
+            if let Some(uv) = mesh.get_uvs().get(&index) {
+                writeln!(out, "vt {} {}", uv.x, uv.y)?;
+                uv_count += 1;
+                written.get_mut(&index).unwrap().1 = Some(uv_count);
+            }
+        }
+        for &index in indices.iter() {
+            if let Some(&normal) = mesh.get_normals().get(&index) {
+                let normal = (normal_matrix * normal).normalize();
+                writeln!(out, "vn {} {} {}", normal.x, normal.y, normal.z)?;
+                normal_count += 1;
+                written.get_mut(&index).unwrap().2 = Some(normal_count);
+            }
+        }
+
+        // Faces split from a polygon as a fan, with no edge between them, are joined back into the polygon
+        let edges: HashSet<(usize, usize)> = mesh
+            .get_edges()
+            .iter()
+            .map(|&(a, b)| (a.min(b), a.max(b)))
+            .collect();
+        // Each polygon along with the group of the faces it was joined from
+        let mut polygons: Vec<(Option<&str>, Vec<usize>)> = vec![];
+        for (i, &(a, b, c)) in mesh.get_faces().iter().enumerate() {
+            if !(written.contains_key(&a) && written.contains_key(&b) && written.contains_key(&c)) {
+                continue;
+            }
+            let group = mesh
+                .groups
                 .iter()
-                .map(|x| x.0)
-                .enumerate()
-                .fold(vec![], |mut accum, (i, v_index)| {
-                    if i == 0 {
-                        accum.push((last_vertex_index, v_index))
-                    } else {
-                        let prev = accum.last().unwrap().1;
-                        accum.push((prev, v_index));
-                    }
-                    accum
-                })
+                .find(|(_, faces)| faces.contains(&i))
+                .map(|(name, _)| name.as_str());
+            match polygons.last_mut() {
+                Some((polygon_group, polygon))
+                    if *polygon_group == group
+                        && polygon[0] == a
+                        && polygon.last() == Some(&b)
+                        && !edges.contains(&(a.min(b), a.max(b))) =>
+                {
+                    polygon.push(c)
+                }
+                _ => polygons.push((group, vec![a, b, c])),
+            }
         }
-        #[inline]
-        fn edges_contains_index(edges: &Vec<(usize, usize)>, value: usize) -> bool {
-            edges.iter().fold(false, |accum, (index1, index2)| {
-                if *index1 == value || *index2 == value {
-                    true
-                } else {
-                    accum
+
+        let mut face_edges = HashSet::new();
+        let mut current_group = None;
+        for (group, mut polygon) in polygons {
+            if group != current_group {
+                // Faces outside every group go back to the one the OBJ format starts with
+                writeln!(out, "g {}", group.unwrap_or("default"))?;
+                current_group = group;
+            }
+            for (i, &a) in polygon.iter().enumerate() {
+                let b = polygon[(i + 1) % polygon.len()];
+                face_edges.insert((a.min(b), a.max(b)));
+            }
+            if mirrored {
+                // Mirroring turns the faces inside out, so they are wound the other way to keep facing outwards
+                polygon[1..].reverse();
+            }
+            let corners: Vec<(usize, Option<usize>, Option<usize>)> =
+                polygon.iter().map(|index| written[index]).collect();
+            // A corner can only be given a UV or normal if they all have one
+            let uvs = corners.iter().all(|corner| corner.1.is_some());
+            let normals = corners.iter().all(|corner| corner.2.is_some());
+            write!(out, "f")?;
+            for (position, uv, normal) in corners {
+                match (uvs, normals) {
+                    (true, true) => write!(out, " {position}/{}/{}", uv.unwrap(), normal.unwrap())?,
+                    (true, false) => write!(out, " {position}/{}", uv.unwrap())?,
+                    (false, true) => write!(out, " {position}//{}", normal.unwrap())?,
+                    (false, false) => write!(out, " {position}")?,
                 }
-            })
+            }
+            writeln!(out)?;
+        }
+        for &(a, b) in mesh.get_edges() {
+            if face_edges.contains(&(a.min(b), a.max(b))) {
+                continue;
+            }
+            if let (Some(&(a, _, _)), Some(&(b, _, _))) = (written.get(&a), written.get(&b)) {
+                writeln!(out, "l {a} {b}")?;
+            }
         }
+    }
+    Ok(())
+}
 
-        self.0
-            .data
-            .objects
+/// The matrix that takes normals to where the given matrix takes the surfaces they belong to (the inverse transpose, up to scale), and whether the matrix mirrors things.
+fn normal_matrix(matrix: &Matrix4) -> (Matrix3, bool) {
+    let row = |i: usize| vec3!(matrix.rows[i][0], matrix.rows[i][1], matrix.rows[i][2]);
+    let (x, y, z) = (row(0), row(1), row(2));
+    let (x_cofactor, y_cofactor, z_cofactor) = (y.cross(z), z.cross(x), x.cross(y));
+    let mirrored = x.dot(x_cofactor) < 0.0;
+    let sign = if mirrored { -1.0 } else { 1.0 };
+    let rows =
+        [x_cofactor, y_cofactor, z_cofactor].map(|row| [row.x * sign, row.y * sign, row.z * sign]);
+    (Matrix3::new(rows), mirrored)
+}
+
+impl std::convert::From<AsciiObj> for Vec<Mesh> {
+    fn from(ascii_obj: AsciiObj) -> Self {
+        //! Makes a mesh for every object in the file, named after it. Each polygon's outline becomes edges, and polygons with more than three corners are split into triangles as a fan for the faces, with the faces of each of the object's groups kept together in ```groups```.
+        //! Every mesh only holds the vertices its object uses, numbered from 0 in the order the object first uses them, and an edge shared by several polygons is only kept once.
+        //! Normals and UVs are kept for every vertex that has them. Where the faces around a vertex give it different normals, they are averaged (unless they cancel out, in which case the vertex gets none), and where they give it different UVs, the first one is kept.
+        let data = &ascii_obj.0.data;
+        data.objects
             .iter()
-            .map(|object| {
-                //Object to mesh
-                let edges: Vec<(usize, usize)> = object
-                    .groups
-                    .iter()
-                    .map(|group| group.polys.iter())
-                    .flatten()
-                    .map(|x| x.to_owned())
-                    .map(simple_polygon_to_edges)
-                    .flatten()
-                    .collect();
-
-                let positions: HashMap<usize, Vector3> = self
-                    .0
-                    .data
-                    .position
-                    .iter()
-                    .map(Vector3::from)
-                    .enumerate()
-                    .filter(|(i, _)| edges_contains_index(&edges, *i))
-                    .fold(HashMap::new(), |mut accum, (i, pos)| {
-                        accum.insert(i, pos);
-                        accum
-                    });
-
-                let mut mesh = Mesh::default();
-                *mesh.get_edges_mut() = edges;
-                *mesh.get_verticies_mut() = positions;
-                mesh
-            })
+            .map(|object| object_mesh(data, object))
             .collect()
     }
 }
 
+/// Makes the mesh of an object in the file.
+fn object_mesh(data: &obj::ObjData, object: &obj::Object) -> Mesh {
+    let mut mesh = Mesh::default();
+    // Index in the file -> index in the mesh
+    let mut remap: HashMap<usize, usize> = HashMap::new();
+    let mut normals: HashMap<usize, Vector3> = HashMap::new();
+    let mut uvs: HashMap<usize, Vector2> = HashMap::new();
+    let mut seen: HashSet<(usize, usize)> = HashSet::new();
+    for group in &object.groups {
+        let first_face = mesh.get_faces().len();
+        for polygon in &group.polys {
+            let corners: Vec<usize> = polygon
+                .0
+                .iter()
+                .map(|&obj::IndexTuple(position, uv, normal)| {
+                    let next = remap.len();
+                    let index = *remap.entry(position).or_insert(next);
+                    if index == next {
+                        if let Some(position) = data.position.get(position) {
+                            mesh.get_verticies_mut().insert(index, position.into());
+                        }
+                    }
+                    if let Some(normal) = normal.and_then(|normal| data.normal.get(normal)) {
+                        *normals.entry(index).or_insert(vec3!(0.0, 0.0, 0.0)) +=
+                            Vector3::from(normal);
+                    }
+                    if let Some(uv) = uv.and_then(|uv| data.texture.get(uv)) {
+                        uvs.entry(index).or_insert(vec2!(uv[0], uv[1]));
+                    }
+                    index
+                })
+                .collect();
+            for (i, &a) in corners.iter().enumerate() {
+                let b = corners[(i + 1) % corners.len()];
+                if a != b && seen.insert((a.min(b), a.max(b))) {
+                    mesh.add_edge((a, b));
+                }
+            }
+            for i in 1..corners.len().saturating_sub(1) {
+                mesh.add_face((corners[0], corners[i], corners[i + 1]));
+            }
+        }
+        let faces = first_face..mesh.get_faces().len();
+        if !faces.is_empty() {
+            mesh.groups.push((group.name.clone(), faces));
+        }
+    }
+    // Opposite normals cancel out, leaving nothing to point along
+    normals.retain(|_, normal| normal.len() > 1e-6);
+    normals
+        .values_mut()
+        .for_each(|normal| *normal = normal.normalize());
+
+    *mesh.get_normals_mut() = normals;
+    *mesh.get_uvs_mut() = uvs;
+    mesh.object = Some(object.name.clone());
+    mesh
+}
+
+impl std::convert::From<AsciiObj> for Scene {
+    fn from(ascii_obj: AsciiObj) -> Self {
+        //! Puts every object of the OBJ file in its own root node, named after the object and holding its mesh, so that the objects of a model can be found with ```find()``` and moved on their own. Objects that share a name still get a node each.
+        let mut scene = Scene::new();
+        for mesh in Vec::<Mesh>::from(ascii_obj) {
+            let name = mesh.object.clone().unwrap_or_default();
+            scene.add(None, Node::with_mesh(&name, mesh));
+        }
+        scene
+    }
+}
+
 impl std::convert::From<&[f32; 3]> for Vector3 {
     fn from(value: &[f32; 3]) -> Self {
         vec3!(value[0], value[1], value[2],)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(source: &str) -> AsciiObj {
+        AsciiObj(Obj {
+            data: obj::ObjData::load_buf(source.as_bytes()).unwrap(),
+            path: std::path::PathBuf::new(),
+        })
+    }
+
+    const TWO_PARTS: &str = "
+v 0 0 0
+v 1 0 0
+v 1 1 0
+v 0 1 0
+v 0 0 1
+vt 0 0
+vt 1 0
+vt 1 1
+vt 0 1
+vn 0 0 1
+vn 0 -1 0
+o body
+g front
+f 1/1/1 2/2/1 3/3/1 4/4/1
+g back
+f 1/1/2 2/2/2 5/4/2
+o wheel
+f 3 4 5
+";
+
+    #[test]
+    fn faces_normals_and_names() {
+        let meshs: Vec<Mesh> = parse(TWO_PARTS).into();
+        let names: Vec<Option<&str>> = meshs.iter().map(|mesh| mesh.object.as_deref()).collect();
+        assert_eq!(names, [Some("body"), Some("wheel")]);
+
+        // The quad is split into two triangles, and the faces of each group are kept together
+        let body = &meshs[0];
+        assert_eq!(body.get_faces(), &vec![(0, 1, 2), (0, 2, 3), (0, 1, 4)]);
+        assert_eq!(
+            body.groups,
+            vec![("front".to_owned(), 0..2), ("back".to_owned(), 2..3)]
+        );
+        assert_eq!(body.get_group_faces("back"), Some(&[(0, 1, 4)][..]));
+        assert_eq!(body.get_group_faces("wheel"), None);
+        // Only the vertices an object uses are kept, numbered from 0, and the groups share them
+        assert_eq!(body.get_verticies().len(), 5);
+        assert_eq!(body.get_verticies()[&4], vec3!(0.0, 0.0, 1.0));
+        assert_eq!(
+            body.get_edges(),
+            &vec![(0, 1), (1, 2), (2, 3), (3, 0), (1, 4), (4, 0)]
+        );
+        assert_eq!(body.get_normals()[&2], vec3!(0.0, 0.0, 1.0));
+        assert_eq!(body.get_normals()[&4], vec3!(0.0, -1.0, 0.0));
+        assert_eq!(body.get_uvs()[&3], vec2!(0.0, 1.0));
+        assert_eq!(body.get_uvs()[&4], vec2!(0.0, 1.0));
+
+        // Vertices without normals or UVs in the file don't get any
+        let wheel = &meshs[1];
+        assert_eq!(wheel.get_faces(), &vec![(0, 1, 2)]);
+        assert_eq!(wheel.groups, vec![("default".to_owned(), 0..1)]);
+        assert!(wheel.get_normals().is_empty() && wheel.get_uvs().is_empty());
+    }
+
+    #[test]
+    fn averaged_normals() {
+        let meshs: Vec<Mesh> = parse(
+            "
+v 0 0 0
+v 1 0 0
+v 0 1 0
+v 0 0 1
+vn 1 0 0
+vn 0 1 0
+f 1//1 2//1 3//1
+f 1//2 3//2 4//2
+",
+        )
+        .into();
+        let normal = meshs[0].get_normals()[&0];
+        assert!(normal.approx_eq(vec3!(1.0, 1.0, 0.0).normalize(), 1e-5));
+        assert_eq!(meshs[0].get_normals()[&1], vec3!(1.0, 0.0, 0.0));
+
+        // A vertex shared by faces with opposite normals is left without one
+        let meshs: Vec<Mesh> = parse(
+            "
+v 0 0 0
+v 1 0 0
+v 0 1 0
+v 0 0 1
+vn 1 0 0
+vn -1 0 0
+f 1//1 2//1 3//1
+f 1//2 3//2 4//2
+",
+        )
+        .into();
+        let normals = meshs[0].get_normals();
+        assert!(!normals.contains_key(&0) && !normals.contains_key(&2));
+        assert_eq!(normals[&3], vec3!(-1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn shared_edges() {
+        let meshs: Vec<Mesh> = parse(
+            "
+v 0 0 0
+v 1 0 0
+v 1 1 0
+v 0 1 0
+v 5 5 5
+f 1 2 3
+f 1 3 4
+f 4 3 1
+",
+        )
+        .into();
+        let mesh = &meshs[0];
+        // The diagonal is shared by both triangles and the last one repeats the second backwards
+        assert_eq!(mesh.get_edges().len(), 5);
+        assert_eq!(mesh.get_faces().len(), 3);
+        // Vertex 5 isn't used by any polygon
+        assert_eq!(mesh.get_verticies().len(), 4);
+    }
+
+    fn write(meshs: &[Mesh], bake_transforms: bool) -> String {
+        let mut out = vec![];
+        AsciiObj::write_meshs(&mut out, meshs, bake_transforms).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn export_round_trip() {
+        let meshs: Vec<Mesh> = parse(TWO_PARTS).into();
+        let exported = write(&meshs, false);
+        assert_eq!(exported.matches("o body").count(), 1);
+        let reloaded: Vec<Mesh> = parse(&exported).into();
+        assert_eq!(reloaded.len(), meshs.len());
+        for (mesh, reloaded) in meshs.iter().zip(reloaded.iter()) {
+            assert_eq!(reloaded.object, mesh.object);
+            assert_eq!(reloaded.groups, mesh.groups);
+            assert_eq!(reloaded.get_verticies(), mesh.get_verticies());
+            assert_eq!(reloaded.get_faces(), mesh.get_faces());
+            assert_eq!(reloaded.get_edges(), mesh.get_edges());
+            assert_eq!(reloaded.get_uvs(), mesh.get_uvs());
+            let normals = mesh.get_normals();
+            assert_eq!(reloaded.get_normals().len(), normals.len());
+            assert!(reloaded
+                .get_normals()
+                .iter()
+                .all(|(index, normal)| normal.approx_eq(normals[index], 1e-5)));
+        }
+
+        // Faces added after the groups aren't put in the last one
+        let mut mesh = meshs[0].clone();
+        mesh.add_face((4, 3, 2));
+        let reloaded: Vec<Mesh> = parse(&write(&[mesh], false)).into();
+        assert_eq!(reloaded[0].groups[2], ("default".to_owned(), 3..4));
+
+        // Transforms are only kept when they are baked in
+        let mut cube = crate::primitives::cube(1.0);
+        cube.position = vec3!(10.0, 0.0, 0.0);
+        cube.rotation = vec3!(0.0, 1.0, 0.0);
+        let unbaked: Vec<Mesh> = parse(&write(std::slice::from_ref(&cube), false)).into();
+        let baked: Vec<Mesh> = parse(&write(std::slice::from_ref(&cube), true)).into();
+        assert_eq!(unbaked[0].object.as_deref(), Some("mesh_1"));
+        let mut expected: Vec<Vector3> = cube.get_global_verticies().into_values().collect();
+        let mut baked: Vec<Vector3> = baked[0].get_verticies().values().copied().collect();
+        let key = |v: &Vector3| {
+            (v.x * 1e3) as i64 * 1_000_000 + (v.y * 1e3) as i64 * 1000 + (v.z * 1e3) as i64
+        };
+        expected.sort_by_key(key);
+        baked.sort_by_key(key);
+        assert!(expected
+            .iter()
+            .zip(baked.iter())
+            .all(|(a, b)| a.approx_eq(*b, 1e-4)));
+        assert_eq!(unbaked[0].get_faces().len(), cube.get_faces().len());
+    }
+
+    #[test]
+    fn export_lines() {
+        let mut mesh = Mesh::default();
+        mesh.insert_vertices(vec![
+            (3, vec3!(0.0, 0.0, 0.0)),
+            (7, vec3!(1.0, 0.0, 0.0)),
+            (9, vec3!(0.0, 1.0, 0.0)),
+            (12, vec3!(0.0, 0.0, 1.0)),
+        ]);
+        mesh.add_edges(vec![(3, 7), (7, 9), (9, 3), (3, 12)]);
+        assert_eq!(
+            write(std::slice::from_ref(&mesh), false),
+            "# Exported by ascii_renderer\no mesh_1\nv 0 0 0\nv 1 0 0\nv 0 1 0\nv 0 0 1\nl 1 2\nl 2 3\nl 3 1\nl 1 4\n"
+        );
+        // Only the edge that isn't part of the face is left as a line
+        mesh.add_face((3, 7, 9));
+        let exported = write(&[mesh.clone(), mesh], false);
+        assert!(exported.ends_with("f 5 6 7\nl 5 8\n"));
+        assert_eq!(exported.matches("\nl ").count(), 2);
+        // Faces split from a polygon are joined back into it
+        let exported = write(&[crate::primitives::cube(1.0)], false);
+        assert_eq!(exported.matches("\nf ").count(), 6);
+        assert!(!exported.contains("\nl "));
+    }
+
+    #[test]
+    fn export_scene() {
+        let mut scene = Scene::new();
+        let mut mirror = Node::new("mirror");
+        mirror.transform.scale = vec3!(-1.0, 1.0, 1.0);
+        let mirror = scene.add(None, mirror);
+        let mut triangle = Mesh::default();
+        triangle.insert_vertices(vec![
+            (0, vec3!(1.0, 0.0, 0.0)),
+            (1, vec3!(2.0, 0.0, 0.0)),
+            (2, vec3!(1.0, 1.0, 0.0)),
+        ]);
+        triangle.add_face((0, 1, 2));
+        for index in 0..3 {
+            triangle
+                .get_normals_mut()
+                .insert(index, vec3!(0.0, 0.0, 1.0));
+        }
+        scene.add(Some(mirror), Node::with_mesh("triangle", triangle.clone()));
+        let mut hidden = Node::with_mesh("hidden", triangle);
+        hidden.visible = false;
+        scene.add(None, hidden);
+
+        let mut out = vec![];
+        AsciiObj::write_scene(&mut out, &scene).unwrap();
+        let meshs: Vec<Mesh> = parse(&String::from_utf8(out).unwrap()).into();
+        assert_eq!(meshs.len(), 1);
+        let mesh = &meshs[0];
+        assert_eq!(mesh.object.as_deref(), Some("triangle"));
+        assert!(mesh
+            .get_verticies()
+            .values()
+            .any(|&vertex| vertex == vec3!(-2.0, 0.0, 0.0)));
+        // Mirrored faces are turned back the right way around, so they still face the same way as their normals
+        let (a, b, c) = mesh.get_faces()[0];
+        let vertices = mesh.get_verticies();
+        let normal = (vertices[&b] - vertices[&a]).cross(vertices[&c] - vertices[&a]);
+        assert!(normal.z > 0.0);
+        assert_eq!(mesh.get_normals()[&0], vec3!(0.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn scene_of_objects() {
+        let scene: Scene = parse(TWO_PARTS).into();
+        let names: Vec<&str> = scene.iter().map(|(_, node)| node.name.as_str()).collect();
+        assert_eq!(names, ["body", "wheel"]);
+        assert_eq!(scene.roots().len(), 2);
+        let body = scene.find("body").unwrap();
+        assert_eq!(scene[body].mesh.as_ref().unwrap().groups.len(), 2);
+
+        // Objects with the same name are still kept apart
+        let scene: Scene = parse(
+            "
+v 0 0 0
+v 1 0 0
+v 0 1 0
+o part
+f 1 2 3
+o part
+f 3 2 1
+",
+        )
+        .into();
+        assert_eq!(scene.roots().len(), 2);
+        assert!(scene
+            .roots()
+            .iter()
+            .all(|&root| scene[root].name == "part" && scene[root].mesh.is_some()));
+    }
+}
@@ -0,0 +1,264 @@
+use super::canvas::Canvas;
+use super::runner::Screen;
+use crossterm::cursor::MoveTo;
+use crossterm::style::{Color, ResetColor, SetBackgroundColor, SetForegroundColor};
+use crossterm::terminal::{Clear, ClearType};
+use std::io::Write;
+
+///A color buffer with two square-ish pixels in every terminal cell, shown using the ```▀``` and ```▄``` half block glyphs with separate foreground and background colors.
+/// Lines, triangles and meshs can be drawn to it like any other Canvas, in which case every pixel drawn takes the color of the pen. The renderer sets ```mesh_color``` to each mesh's color before drawing it, which is used instead of the pen for meshs that have one.
+/// A runner shows it directly when its logic implements ```Logic<PixelBuffer>```.
+/// # Example
+/// ```
+/// # use ascii_renderer::prelude::*;
+/// let mut buf = PixelBuffer::new(3, 2); //3 x 2 cells, which is 3 x 4 pixels
+/// buf.set_pixel(1, 3, Some(Color::Red));
+/// buf.pen = Color::Blue;
+/// buf.draw_line(Line {
+///     stroke: Stroke::Char('+'),
+///     points: (vec2!(0.0, 0.0), vec2!(2.0, 0.0)),
+/// });
+/// assert_eq!(buf.get_pixel(2, 0), Some(Some(Color::Blue)));
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct PixelBuffer {
+    ///The color of every pixel, indexed by [y][x]. None is the terminal's own background.
+    pub data: Vec<Vec<Option<Color>>>,
+    ///The dimensions in cells, not pixels.
+    pub dimensions: (usize, usize),
+    ///The color used for anything drawn through the Canvas trait, unless mesh_color is set.
+    pub pen: Color,
+    ///The color of the mesh currently being drawn, set through ```set_color()```. None means the pen's color is used.
+    pub mesh_color: Option<Color>,
+}
+
+impl PixelBuffer {
+    pub fn new(width: usize, height: usize) -> Self {
+        //! Creates a buffer that is width x height cells, meaning width x (height * 2) pixels.
+        PixelBuffer {
+            data: vec![vec![None; width]; height * 2],
+            dimensions: (width, height),
+            pen: Color::White,
+            mesh_color: None,
+        }
+    }
+    pub fn get_pixel(&self, x: usize, y: usize) -> Option<Option<Color>> {
+        self.data.get(y)?.get(x).copied()
+    }
+    pub fn set_pixel(&mut self, x: usize, y: usize, color: Option<Color>) {
+        //! Sets a pixel's color. Out of range pixels are ignored.
+        if let Some(pixel) = self.data.get_mut(y).and_then(|row| row.get_mut(x)) {
+            *pixel = color;
+        }
+    }
+    pub fn resize(&mut self, width: usize, height: usize, clear: bool) {
+        //! Changes the dimensions of the buffer, in cells. If clear is false, whatever lies within both the old and new dimensions is kept and any new pixels are empty, otherwise the whole buffer is empty afterwards.
+        if clear {
+            self.fill(None);
+        }
+        self.data.resize(height * 2, vec![None; width]);
+        for row in self.data.iter_mut() {
+            row.resize(width, None);
+        }
+        self.dimensions = (width, height);
+    }
+    pub fn fill(&mut self, color: Option<Color>) {
+        //! Fills the buffer with the given color
+        for row in self.data.iter_mut() {
+            for item in row.iter_mut() {
+                *item = color;
+            }
+        }
+    }
+}
+
+impl Clone for PixelBuffer {
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data.clone(),
+            dimensions: self.dimensions,
+            pen: self.pen,
+            mesh_color: self.mesh_color,
+        }
+    }
+    fn clone_from(&mut self, source: &Self) {
+        //! Reuses the rows that are already allocated, the same as for a CharBuffer.
+        self.data.clone_from(&source.data);
+        self.dimensions = source.dimensions;
+        self.pen = source.pen;
+        self.mesh_color = source.mesh_color;
+    }
+}
+
+impl Canvas for PixelBuffer {
+    fn dimensions(&self) -> (usize, usize) {
+        (self.dimensions.0, self.dimensions.1 * 2)
+    }
+    fn plot(&mut self, x: usize, y: usize, char: char) {
+        //! Pixels plotted with a space are cleared, anything else is set to the mesh color, or the pen's color if there is none.
+        let color = if char == ' ' {
+            None
+        } else {
+            Some(self.mesh_color.unwrap_or(self.pen))
+        };
+        self.set_pixel(x, y, color);
+    }
+    fn set_color(&mut self, color: Option<Color>) {
+        //! Meshs without a color go back to being drawn with the pen.
+        self.mesh_color = color;
+    }
+}
+
+impl Screen for PixelBuffer {
+    fn new(width: usize, height: usize) -> Self {
+        PixelBuffer::new(width, height)
+    }
+    fn resize(&mut self, width: usize, height: usize, clear: bool) {
+        PixelBuffer::resize(self, width, height, clear);
+    }
+    fn size_for_terminal(&self, columns: u16, rows: u16) -> (usize, usize) {
+        //! Every cell is a single column wide.
+        (columns as usize, rows.saturating_sub(1) as usize)
+    }
+    fn write_to(&self, out: &mut dyn Write) -> std::io::Result<()> {
+        //! Every cell becomes a half block with the top pixel as one color and the bottom pixel as the other. Colors are only sent when they change from the previous cell, and are reset at the end of every row.
+        for rows in self.data.chunks(2) {
+            let mut colors = (None, None);
+            for (&top, &bottom) in rows[0].iter().zip(rows[1].iter()) {
+                write_cell(out, top, bottom, &mut colors)?;
+            }
+            writeln!(out, "{ResetColor}")?;
+        }
+        Ok(())
+    }
+    fn write_changes(&self, previous: &Self, out: &mut dyn Write) -> std::io::Result<()> {
+        //! Writes each cell where either pixel changed in place, only moving the cursor when the next changed cell isn't right after the last one written. Colors are sent the same way as for a whole frame, and reset once at the end. If the size changed since previous, the terminal is cleared and the whole buffer is written instead.
+        if self.dimensions != previous.dimensions {
+            // The last frame may have covered parts of the terminal that this one doesn't
+            write!(out, "{}", Clear(ClearType::All))?;
+            return self.redraw(out);
+        }
+        let mut colors = (None, None);
+        let mut cursor = None;
+        for (y, (rows, previous_rows)) in
+            self.data.chunks(2).zip(previous.data.chunks(2)).enumerate()
+        {
+            for x in 0..self.dimensions.0 {
+                let (top, bottom) = (rows[0][x], rows[1][x]);
+                if (top, bottom) == (previous_rows[0][x], previous_rows[1][x]) {
+                    continue;
+                }
+                if cursor != Some((x, y)) {
+                    write!(out, "{}", MoveTo(x as u16, y as u16))?;
+                }
+                write_cell(out, top, bottom, &mut colors)?;
+                cursor = Some((x + 1, y));
+            }
+        }
+        if colors != (None, None) {
+            write!(out, "{ResetColor}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes a cell with the given top and bottom pixels, sending only the colors that differ from colors (the foreground and background currently set), which is then updated to match.
+fn write_cell(
+    out: &mut dyn Write,
+    top: Option<Color>,
+    bottom: Option<Color>,
+    colors: &mut (Option<Color>, Option<Color>),
+) -> std::io::Result<()> {
+    let (foreground, background) = colors;
+    // The glyph is drawn in the foreground color, the rest of the cell is the background
+    let (glyph, cell_foreground, cell_background) = match (top, bottom) {
+        (None, None) => (' ', *foreground, None),
+        (Some(top), _) => ('▀', Some(top), bottom),
+        (None, Some(bottom)) => ('▄', Some(bottom), None),
+    };
+    if cell_background != *background {
+        match cell_background {
+            Some(color) => write!(out, "{}", SetBackgroundColor(color))?,
+            None => write!(out, "{}", SetBackgroundColor(Color::Reset))?,
+        }
+        *background = cell_background;
+    }
+    if cell_foreground != *foreground {
+        if let Some(color) = cell_foreground {
+            write!(out, "{}", SetForegroundColor(color))?;
+        }
+        *foreground = cell_foreground;
+    }
+    write!(out, "{glyph}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn half_block_output() {
+        let mut buf = PixelBuffer::new(4, 1);
+        buf.set_pixel(0, 0, Some(Color::Red));
+        buf.set_pixel(1, 0, Some(Color::Red));
+        buf.set_pixel(1, 1, Some(Color::Blue));
+        buf.set_pixel(2, 1, Some(Color::Green));
+
+        let mut out = vec![];
+        buf.write_to(&mut out).unwrap();
+        let expected = format!(
+            "{}▀{}▀{}{}▄ {}\n",
+            SetForegroundColor(Color::Red),
+            SetBackgroundColor(Color::Blue),
+            SetBackgroundColor(Color::Reset),
+            SetForegroundColor(Color::Green),
+            ResetColor,
+        );
+        assert_eq!(String::from_utf8(out).unwrap(), expected);
+    }
+
+    #[test]
+    fn only_changed_cells_are_written() {
+        let previous = PixelBuffer::new(4, 1);
+        let mut buf = previous.clone();
+        buf.set_pixel(1, 0, Some(Color::Red));
+        buf.set_pixel(2, 1, Some(Color::Blue));
+        buf.set_pixel(3, 0, Some(Color::Blue));
+        let mut out = vec![];
+        buf.write_changes(&previous, &mut out).unwrap();
+        let expected = format!(
+            "{}{}▀{}▄▀{}",
+            MoveTo(1, 0),
+            SetForegroundColor(Color::Red),
+            SetForegroundColor(Color::Blue),
+            ResetColor,
+        );
+        assert_eq!(String::from_utf8(out).unwrap(), expected);
+
+        let mut out = vec![];
+        previous.clone().write_changes(&previous, &mut out).unwrap();
+        assert!(out.is_empty());
+
+        // A smaller frame is written on a cleared terminal
+        let mut smaller = buf.clone();
+        smaller.resize(1, 1, false);
+        let mut out = vec![];
+        smaller.write_changes(&buf, &mut out).unwrap();
+        let expected = format!("{}{} {}\n", Clear(ClearType::All), MoveTo(0, 0), ResetColor);
+        assert_eq!(String::from_utf8(out).unwrap(), expected);
+    }
+
+    #[test]
+    fn plotting_uses_the_pen() {
+        let mut buf = PixelBuffer::new(1, 1);
+        buf.set_color(Some(Color::Yellow));
+        buf.plot(0, 1, '+');
+        buf.set_color(None);
+        buf.plot(0, 0, '#');
+        assert_eq!(buf.get_pixel(0, 0), Some(Some(Color::White)));
+        assert_eq!(buf.get_pixel(0, 1), Some(Some(Color::Yellow)));
+        assert_eq!(buf.pen, Color::White);
+        buf.plot(0, 0, ' ');
+        assert_eq!(buf.get_pixel(0, 0), Some(None));
+    }
+}
@@ -0,0 +1,352 @@
+/*!
+Turns functions into meshs, for showing 3D plots in the terminal, along with axes to read them by.
+Plots use their own coordinates where z is the height, as is usual for plots. Since the screen's y grows downwards, a plot point (x, y, z) is placed in the world at (x, -z, y), so that higher values point up the screen (see ```to_world()```).
+# Example
+```
+# use ascii_renderer::prelude::*;
+use ascii_renderer::plot::{self, Axes, IsoLines};
+
+let surface = plot::surface(|x, y| (x * x + y * y).sqrt().sin(), -3.0..3.0, -3.0..3.0, (24, 24), IsoLines::Both);
+let axes = Axes::new(-3.0..3.0, -3.0..3.0, -1.0..1.0);
+
+let mut camera = Camera::new_auto_aspect(vec3!(6.0, -5.0, -8.0), vec3!(0.0, 0.0, 0.0), 1.0);
+camera.look_at(vec3!(0.0, 0.0, 0.0), vec3!(0.0, -1.0, 0.0));
+let mut renderer = Renderer::new(vec![surface, axes.mesh()], camera);
+renderer.labels = axes.labels();
+
+let mut buf = CharBuffer::new(60, 30);
+renderer.draw(&mut buf);
+```
+*/
+use super::rendering::{Label, Mesh};
+use super::{vec3, Vector3};
+use std::ops::Range;
+
+/// Which lines of a plot's grid are drawn. Surfaces always have every face, whichever lines are drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IsoLines {
+    /// Every line of the grid.
+    #[default]
+    Both,
+    /// Only the lines along which u (or x, for ```surface()```) changes, one for every sampled v (or y).
+    AlongU,
+    /// Only the lines along which v (or y, for ```surface()```) changes, one for every sampled u (or x).
+    AlongV,
+}
+
+/// Places a point in plot coordinates, where z is the height, in the world.
+pub fn to_world(point: Vector3) -> Vector3 {
+    vec3!(point.x, -point.z, point.y)
+}
+
+pub fn surface(
+    f: impl Fn(f32, f32) -> f32,
+    x_range: Range<f32>,
+    y_range: Range<f32>,
+    resolution: (usize, usize),
+    lines: IsoLines,
+) -> Mesh {
+    //! Plots z = f(x, y) over the given ranges (including both ends), sampled on a grid of resolution.0 x resolution.1 cells (at least 1 each way).
+    //! Points where f isn't finite (such as dividing by zero) are left out, along with every line and face that touches them, leaving a hole.
+    parametric(
+        |x, y| vec3!(x, y, f(x, y)),
+        x_range,
+        y_range,
+        resolution,
+        lines,
+    )
+}
+
+pub fn parametric(
+    f: impl Fn(f32, f32) -> Vector3,
+    u_range: Range<f32>,
+    v_range: Range<f32>,
+    resolution: (usize, usize),
+    lines: IsoLines,
+) -> Mesh {
+    //! Plots the surface made of the points f(u, v) over the given ranges (including both ends), sampled on a grid of resolution.0 x resolution.1 cells (at least 1 each way). The points are in plot coordinates.
+    //! As with ```surface()```, points that aren't finite leave a hole.
+    //! # Example
+    //! ```
+    //! # use ascii_renderer::prelude::*;
+    //! use ascii_renderer::plot::{self, IsoLines};
+    //! use std::f32::consts::TAU;
+    //!
+    //! // * A cylinder, drawn as just the circles around it
+    //! let cylinder = plot::parametric(
+    //!     |u, v| vec3!(u.cos(), u.sin(), v),
+    //!     0.0..TAU,
+    //!     0.0..2.0,
+    //!     (16, 4),
+    //!     IsoLines::AlongU,
+    //! );
+    //! assert_eq!(cylinder.get_edges().len(), 16 * 5);
+    //! ```
+    let (columns, rows) = (resolution.0.max(1), resolution.1.max(1));
+    let at = |column: usize, row: usize| row * (columns + 1) + column;
+    let sample = |start: f32, end: f32, i: usize, count: usize| {
+        start + (end - start) * i as f32 / count as f32
+    };
+
+    let mut mesh = Mesh::default();
+    for row in 0..=rows {
+        let v = sample(v_range.start, v_range.end, row, rows);
+        for column in 0..=columns {
+            let u = sample(u_range.start, u_range.end, column, columns);
+            let point = f(u, v);
+            if point.x.is_finite() && point.y.is_finite() && point.z.is_finite() {
+                mesh.insert_vertex(at(column, row), to_world(point));
+            }
+        }
+    }
+
+    let exists = |index: usize| mesh.get_verticies().contains_key(&index);
+    let mut edges = vec![];
+    let mut faces = vec![];
+    for row in 0..=rows {
+        for column in 0..=columns {
+            let corner = at(column, row);
+            if !exists(corner) {
+                continue;
+            }
+            if column < columns && lines != IsoLines::AlongV && exists(at(column + 1, row)) {
+                edges.push((corner, at(column + 1, row)));
+            }
+            if row < rows && lines != IsoLines::AlongU && exists(at(column, row + 1)) {
+                edges.push((corner, at(column, row + 1)));
+            }
+            if column < columns && row < rows {
+                let (right, down, across) = (
+                    at(column + 1, row),
+                    at(column, row + 1),
+                    at(column + 1, row + 1),
+                );
+                if exists(right) && exists(down) && exists(across) {
+                    faces.push((corner, right, across));
+                    faces.push((corner, across, down));
+                }
+            }
+        }
+    }
+    mesh.add_edges(edges);
+    mesh.add_faces(faces);
+    mesh
+}
+
+///A set of 3D axes, with evenly spaced ticks and labels showing their values, for reading a plot by. The axes run along the edges of the box the ranges make that are closest to where the ranges start, and everything is in plot coordinates.
+/// Add the mesh to the renderer's meshs and the labels to its labels.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Axes {
+    pub x: Range<f32>,
+    pub y: Range<f32>,
+    pub z: Range<f32>,
+    ///How many ticks each axis has, including one at each end (at least 2).
+    pub ticks: usize,
+    ///How long each tick is, in plot units.
+    pub tick_length: f32,
+    ///The names shown at the far end of each axis.
+    pub names: (String, String, String),
+}
+
+impl Axes {
+    pub fn new(x: Range<f32>, y: Range<f32>, z: Range<f32>) -> Self {
+        //! Axes with 5 ticks each, named x, y and z. The ticks are a fiftieth of the longest axis long.
+        let longest = (x.end - x.start)
+            .abs()
+            .max((y.end - y.start).abs())
+            .max((z.end - z.start).abs());
+        Axes {
+            x,
+            y,
+            z,
+            ticks: 5,
+            tick_length: longest / 50.0,
+            names: ("x".to_owned(), "y".to_owned(), "z".to_owned()),
+        }
+    }
+    pub fn mesh(&self) -> Mesh {
+        //! The lines of the axes along with their ticks. Each tick sticks out away from the box that the ranges make.
+        let mut mesh = Mesh::default();
+        let mut add_line = |start: Vector3, end: Vector3| {
+            let index = mesh.get_verticies().len();
+            mesh.insert_vertex(index, to_world(start));
+            mesh.insert_vertex(index + 1, to_world(end));
+            mesh.add_edge((index, index + 1));
+        };
+        for axis in self.axis_lines() {
+            add_line(axis.start, axis.end);
+            for (tick, _) in self.tick_values(&axis) {
+                add_line(tick, tick + axis.outwards * self.tick_length);
+            }
+        }
+        mesh
+    }
+    pub fn labels(&self) -> Vec<Label> {
+        //! A label with the value of every tick, placed just past the end of the tick, and one with each axis's name just past its far end.
+        let mut labels = vec![];
+        let names = [&self.names.0, &self.names.1, &self.names.2];
+        for (axis, name) in self.axis_lines().into_iter().zip(names) {
+            for (tick, value) in self.tick_values(&axis) {
+                let position = tick + axis.outwards * (self.tick_length * 4.0);
+                labels.push(Label::new(
+                    to_world(position),
+                    &format_tick(value, axis.step(self.ticks)),
+                ));
+            }
+            let past_end = axis.end + axis.along * (self.tick_length * 4.0);
+            labels.push(Label::new(to_world(past_end), name));
+        }
+        labels
+    }
+    /// The start and end of each axis, along with the directions it runs in and its ticks stick out in.
+    fn axis_lines(&self) -> [AxisLine; 3] {
+        let origin = vec3!(self.x.start, self.y.start, self.z.start);
+        // Empty ranges run the positive way, so that their axis still has a direction
+        let along = |range: &Range<f32>| if range.end < range.start { -1.0 } else { 1.0 };
+        [
+            AxisLine {
+                start: origin,
+                end: vec3!(self.x.end, self.y.start, self.z.start),
+                along: vec3!(along(&self.x), 0.0, 0.0),
+                outwards: vec3!(0.0, -along(&self.y), 0.0),
+                range: self.x.clone(),
+            },
+            AxisLine {
+                start: origin,
+                end: vec3!(self.x.start, self.y.end, self.z.start),
+                along: vec3!(0.0, along(&self.y), 0.0),
+                outwards: vec3!(-along(&self.x), 0.0, 0.0),
+                range: self.y.clone(),
+            },
+            AxisLine {
+                start: origin,
+                end: vec3!(self.x.start, self.y.start, self.z.end),
+                along: vec3!(0.0, 0.0, along(&self.z)),
+                outwards: vec3!(-along(&self.x), 0.0, 0.0),
+                range: self.z.clone(),
+            },
+        ]
+    }
+    /// The position and value of every tick along an axis.
+    fn tick_values(&self, axis: &AxisLine) -> Vec<(Vector3, f32)> {
+        let ticks = self.ticks.max(2);
+        (0..ticks)
+            .map(|i| {
+                let t = i as f32 / (ticks - 1) as f32;
+                (
+                    axis.start.lerp(axis.end, t),
+                    axis.range.start + (axis.range.end - axis.range.start) * t,
+                )
+            })
+            .collect()
+    }
+}
+
+/// One of the three lines of a set of axes.
+struct AxisLine {
+    start: Vector3,
+    end: Vector3,
+    along: Vector3,
+    outwards: Vector3,
+    range: Range<f32>,
+}
+
+impl AxisLine {
+    /// The difference in value between neighbouring ticks.
+    fn step(&self, ticks: usize) -> f32 {
+        (self.range.end - self.range.start).abs() / (ticks.max(2) - 1) as f32
+    }
+}
+
+/// Formats a tick's value with just enough decimal places to tell it apart from the ticks next to it.
+fn format_tick(value: f32, step: f32) -> String {
+    // The fewest decimal places that show the step exactly, or one more than it takes to show its first digit if that isn't possible
+    let decimals = if step <= 0.0 {
+        0
+    } else {
+        (0..=4)
+            .find(|&decimals| {
+                let scaled = step * 10f32.powi(decimals);
+                (scaled - scaled.round()).abs() < 0.01
+            })
+            .unwrap_or((-step.log10()).ceil().max(0.0) as i32 + 1) as usize
+    };
+    let text = format!("{value:.decimals$}");
+    // Rounding small negative values can leave a -0
+    if text
+        .trim_start_matches('-')
+        .chars()
+        .all(|char| char == '0' || char == '.')
+    {
+        text.trim_start_matches('-').to_owned()
+    } else {
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn surfaces() {
+        let mesh = surface(|x, y| x + y, 0.0..2.0, 0.0..1.0, (2, 1), IsoLines::Both);
+        assert_eq!(mesh.get_verticies().len(), 6);
+        assert_eq!(mesh.get_edges().len(), 7);
+        assert_eq!(mesh.get_faces().len(), 4);
+        // Heights point up the screen, which is -y
+        assert_eq!(mesh.get_verticies()[&5], vec3!(2.0, -3.0, 1.0));
+
+        let along_x = surface(|x, y| x * y, 0.0..1.0, 0.0..1.0, (4, 3), IsoLines::AlongU);
+        assert_eq!(along_x.get_edges().len(), 4 * 4);
+        assert!(along_x.get_edges().iter().all(|&(a, b)| b == a + 1));
+        let along_y = surface(|x, y| x * y, 0.0..1.0, 0.0..1.0, (4, 3), IsoLines::AlongV);
+        assert_eq!(along_y.get_edges().len(), 5 * 3);
+
+        // The point at the origin is left out, along with everything touching it
+        let hole = surface(
+            |x, y| 1.0 / (x * x + y * y),
+            -1.0..1.0,
+            -1.0..1.0,
+            (2, 2),
+            IsoLines::Both,
+        );
+        assert_eq!(hole.get_verticies().len(), 8);
+        assert_eq!(hole.get_edges().len(), 8);
+        assert!(hole.get_faces().is_empty());
+    }
+
+    #[test]
+    fn axes() {
+        let mut axes = Axes::new(0.0..10.0, -1.0..1.0, 0.0..0.5);
+        axes.ticks = 3;
+        assert_eq!(axes.tick_length, 0.2);
+        let mesh = axes.mesh();
+        assert_eq!(mesh.get_edges().len(), 3 + 3 * 3);
+        // The x axis runs along the front edge of the box
+        assert_eq!(mesh.get_verticies()[&1], to_world(vec3!(10.0, -1.0, 0.0)));
+
+        let labels = axes.labels();
+        let texts: Vec<&str> = labels.iter().map(|label| label.text.as_str()).collect();
+        assert_eq!(
+            texts,
+            ["0", "5", "10", "x", "-1", "0", "1", "y", "0.00", "0.25", "0.50", "z"]
+        );
+        // Ticks stick out away from the box
+        assert!(labels[0]
+            .position
+            .approx_eq(to_world(vec3!(0.0, -1.8, 0.0)), 1e-4));
+        assert!(labels[4]
+            .position
+            .approx_eq(to_world(vec3!(-0.8, -1.0, 0.0)), 1e-4));
+
+        // A flat range still gets its name placed past the end of its axis
+        let flat = Axes::new(0.0..10.0, 0.0..10.0, 0.0..0.0).labels();
+        let z = flat.iter().find(|label| label.text == "z").unwrap();
+        assert!(z.position.approx_eq(to_world(vec3!(0.0, 0.0, 0.8)), 1e-4));
+
+        assert_eq!(format_tick(-0.001, 0.1), "0.0");
+        assert_eq!(format_tick(2.5, 0.5), "2.5");
+        assert_eq!(format_tick(2.0 / 3.0, 1.0 / 3.0), "0.67");
+    }
+}
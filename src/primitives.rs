@@ -0,0 +1,563 @@
+/*!
+Functions that generate common shapes as meshs, with both edges (for wireframes) and faces (for ```RenderMode::Shaded```), with every face wound so that it faces outwards.
+Everything is centered on the origin, and shapes with a top and bottom point up the screen, which is -y (see ```Camera::look_at()```). Segment counts below the least that makes sense for a shape are raised to it.
+Only the outlines of each polygon become edges, so e.g. a cylinder's sides are drawn as lines down its length rather than as the triangles its faces are split into.
+# Example
+```
+# use ascii_renderer::prelude::*;
+use ascii_renderer::primitives;
+
+let mut meshs = vec![
+    primitives::uv_sphere(1.0, 16, 8),
+    primitives::torus(2.0, 0.5, 24, 8),
+    primitives::grid(vec2!(10.0, 10.0), (10, 10)),
+];
+meshs[2].position = vec3!(0.0, 2.0, 0.0); //A floor beneath the others
+meshs.extend(primitives::axes(3.0));
+```
+*/
+use super::rendering::{perpendicular_to, Mesh};
+use super::{vec3, Vector2, Vector3};
+use crossterm::style::Color;
+use std::collections::{HashMap, HashSet};
+use std::f32::consts::{PI, TAU};
+
+/// Builds a mesh from its vertices and polygons. Every polygon's outline becomes edges (each edge only once, however many polygons share it), and it is split into triangles as a fan for the faces.
+fn from_polygons(vertices: Vec<Vector3>, polygons: &[Vec<usize>]) -> Mesh {
+    let mut mesh = Mesh::default();
+    mesh.insert_vertices(vertices.into_iter().enumerate().collect());
+    let mut seen = HashSet::new();
+    for polygon in polygons {
+        for (i, &a) in polygon.iter().enumerate() {
+            let b = polygon[(i + 1) % polygon.len()];
+            if seen.insert((a.min(b), a.max(b))) {
+                mesh.add_edge((a, b));
+            }
+        }
+        for i in 1..polygon.len().saturating_sub(1) {
+            mesh.add_face((polygon[0], polygon[i], polygon[i + 1]));
+        }
+    }
+    mesh
+}
+
+/// The point at the given angle around a circle of the given radius in the xz plane.
+#[inline]
+fn on_circle(radius: f32, angle: f32, y: f32) -> Vector3 {
+    vec3!(radius * angle.cos(), y, radius * angle.sin())
+}
+
+/// Finds every triangle of three vertices that are all the given distance apart, wound so that it faces away from the origin. For regular polyhedra with triangle faces, those triangles are exactly the faces.
+fn triangles_with_side(vertices: &[Vector3], side: f32) -> Vec<Vec<usize>> {
+    let adjacent =
+        |a: usize, b: usize| (vertices[a].distance(vertices[b]) - side).abs() < side * 0.01;
+    let mut triangles = vec![];
+    for a in 0..vertices.len() {
+        for b in a + 1..vertices.len() {
+            for c in b + 1..vertices.len() {
+                if adjacent(a, b) && adjacent(b, c) && adjacent(a, c) {
+                    triangles.push(outward(vertices, vec![a, b, c]));
+                }
+            }
+        }
+    }
+    triangles
+}
+
+/// Reverses a polygon if it is wound so that it faces the origin.
+fn outward(vertices: &[Vector3], mut polygon: Vec<usize>) -> Vec<usize> {
+    let (a, b, c) = (
+        vertices[polygon[0]],
+        vertices[polygon[1]],
+        vertices[polygon[2]],
+    );
+    if (b - a).cross(c - a).dot(a) < 0.0 {
+        polygon.reverse();
+    }
+    polygon
+}
+
+/// The golden ratio, which the icosahedron and dodecahedron are built from.
+const PHI: f32 = 1.618_034;
+
+pub fn uv_sphere(radius: f32, segments: usize, rings: usize) -> Mesh {
+    //! A sphere made of rings of latitude and lines of longitude, like a globe. Segments is how many lines of longitude there are (at least 3), and rings is how many bands there are from pole to pole (at least 2).
+    let (segments, rings) = (segments.max(3), rings.max(2));
+    let mut vertices = vec![vec3!(0.0, -radius, 0.0)];
+    for ring in 1..rings {
+        let latitude = PI * ring as f32 / rings as f32;
+        for segment in 0..segments {
+            let longitude = TAU * segment as f32 / segments as f32;
+            vertices.push(on_circle(
+                radius * latitude.sin(),
+                longitude,
+                -radius * latitude.cos(),
+            ));
+        }
+    }
+    vertices.push(vec3!(0.0, radius, 0.0));
+
+    let bottom = vertices.len() - 1;
+    let at = |ring: usize, segment: usize| 1 + (ring - 1) * segments + segment % segments;
+    let mut polygons = vec![];
+    for segment in 0..segments {
+        polygons.push(vec![0, at(1, segment), at(1, segment + 1)]);
+        for ring in 1..rings - 1 {
+            polygons.push(vec![
+                at(ring, segment),
+                at(ring + 1, segment),
+                at(ring + 1, segment + 1),
+                at(ring, segment + 1),
+            ]);
+        }
+        polygons.push(vec![
+            bottom,
+            at(rings - 1, segment + 1),
+            at(rings - 1, segment),
+        ]);
+    }
+    from_polygons(vertices, &polygons)
+}
+
+pub fn icosphere(radius: f32, subdivisions: usize) -> Mesh {
+    //! A sphere made of evenly sized triangles, made by splitting each triangle of an icosahedron into four, subdivisions times over. Every subdivision quadruples the number of faces, starting from 20.
+    let icosahedron = icosahedron(1.0);
+    let mut vertices: Vec<Vector3> = (0..12).map(|i| icosahedron.get_verticies()[&i]).collect();
+    let mut triangles: Vec<(usize, usize, usize)> = icosahedron.get_faces().clone();
+    for _ in 0..subdivisions {
+        let mut midpoints: HashMap<(usize, usize), usize> = HashMap::new();
+        let mut midpoint = |a: usize, b: usize, vertices: &mut Vec<Vector3>| {
+            *midpoints.entry((a.min(b), a.max(b))).or_insert_with(|| {
+                vertices.push(((vertices[a] + vertices[b]) / 2.0).normalize());
+                vertices.len() - 1
+            })
+        };
+        triangles = triangles
+            .into_iter()
+            .flat_map(|(a, b, c)| {
+                let ab = midpoint(a, b, &mut vertices);
+                let bc = midpoint(b, c, &mut vertices);
+                let ca = midpoint(c, a, &mut vertices);
+                [(a, ab, ca), (ab, b, bc), (ca, bc, c), (ab, bc, ca)]
+            })
+            .collect();
+    }
+    let vertices = vertices.into_iter().map(|vertex| vertex * radius).collect();
+    let polygons: Vec<Vec<usize>> = triangles
+        .into_iter()
+        .map(|(a, b, c)| vec![a, b, c])
+        .collect();
+    from_polygons(vertices, &polygons)
+}
+
+pub fn cylinder(radius: f32, height: f32, segments: usize) -> Mesh {
+    //! A closed cylinder standing along the y axis, with segments (at least 3) sides around it.
+    let segments = segments.max(3);
+    let mut vertices = vec![];
+    for y in [-height / 2.0, height / 2.0] {
+        for segment in 0..segments {
+            vertices.push(on_circle(radius, TAU * segment as f32 / segments as f32, y));
+        }
+    }
+    let mut polygons: Vec<Vec<usize>> = (0..segments)
+        .map(|i| {
+            let next = (i + 1) % segments;
+            vec![i, segments + i, segments + next, next]
+        })
+        .collect();
+    polygons.push((0..segments).collect());
+    polygons.push((segments..segments * 2).rev().collect());
+    from_polygons(vertices, &polygons)
+}
+
+pub fn cone(radius: f32, height: f32, segments: usize) -> Mesh {
+    //! A closed cone standing along the y axis, with its tip up the screen (towards -y) and segments (at least 3) sides around it.
+    let segments = segments.max(3);
+    let mut vertices: Vec<Vector3> = (0..segments)
+        .map(|segment| on_circle(radius, TAU * segment as f32 / segments as f32, height / 2.0))
+        .collect();
+    vertices.push(vec3!(0.0, -height / 2.0, 0.0));
+    let mut polygons: Vec<Vec<usize>> = (0..segments)
+        .map(|i| vec![segments, i, (i + 1) % segments])
+        .collect();
+    polygons.push((0..segments).rev().collect());
+    from_polygons(vertices, &polygons)
+}
+
+pub fn torus(
+    major_radius: f32,
+    minor_radius: f32,
+    major_segments: usize,
+    minor_segments: usize,
+) -> Mesh {
+    //! A ring lying flat in the xz plane. Major radius is from the center to the middle of the tube, and minor radius is the radius of the tube itself. Major segments is how many sections go around the ring, and minor segments is how many sides the tube has (both at least 3).
+    let (major_segments, minor_segments) = (major_segments.max(3), minor_segments.max(3));
+    let mut vertices = vec![];
+    for major in 0..major_segments {
+        let around = TAU * major as f32 / major_segments as f32;
+        for minor in 0..minor_segments {
+            let tube = TAU * minor as f32 / minor_segments as f32;
+            vertices.push(on_circle(
+                major_radius + minor_radius * tube.cos(),
+                around,
+                minor_radius * tube.sin(),
+            ));
+        }
+    }
+    let at = |major: usize, minor: usize| {
+        (major % major_segments) * minor_segments + minor % minor_segments
+    };
+    let mut polygons = vec![];
+    for major in 0..major_segments {
+        for minor in 0..minor_segments {
+            polygons.push(vec![
+                at(major, minor),
+                at(major, minor + 1),
+                at(major + 1, minor + 1),
+                at(major + 1, minor),
+            ]);
+        }
+    }
+    from_polygons(vertices, &polygons)
+}
+
+pub fn grid(size: Vector2, divisions: (usize, usize)) -> Mesh {
+    //! A flat grid in the xz plane, size.x across and size.y deep, split into divisions.0 x divisions.1 cells (at least 1 each way). Good for a floor.
+    let (columns, rows) = (divisions.0.max(1), divisions.1.max(1));
+    let mut vertices = vec![];
+    for row in 0..=rows {
+        for column in 0..=columns {
+            vertices.push(vec3!(
+                size.x * (column as f32 / columns as f32 - 0.5),
+                0.0,
+                size.y * (row as f32 / rows as f32 - 0.5)
+            ));
+        }
+    }
+    let at = |column: usize, row: usize| row * (columns + 1) + column;
+    let mut polygons = vec![];
+    for row in 0..rows {
+        for column in 0..columns {
+            polygons.push(vec![
+                at(column, row),
+                at(column + 1, row),
+                at(column + 1, row + 1),
+                at(column, row + 1),
+            ]);
+        }
+    }
+    from_polygons(vertices, &polygons)
+}
+
+pub fn plane(size: Vector2) -> Mesh {
+    //! A flat rectangle in the xz plane, size.x across and size.y deep.
+    grid(size, (1, 1))
+}
+
+pub fn pyramid(base: f32, height: f32) -> Mesh {
+    //! A square based pyramid standing along the y axis, with its tip up the screen (towards -y). Base is the length of each side of the base.
+    let half = base / 2.0;
+    let vertices = vec![
+        vec3!(half, height / 2.0, half),
+        vec3!(-half, height / 2.0, half),
+        vec3!(-half, height / 2.0, -half),
+        vec3!(half, height / 2.0, -half),
+        vec3!(0.0, -height / 2.0, 0.0),
+    ];
+    let polygons = [
+        vec![3, 2, 1, 0],
+        vec![4, 0, 1],
+        vec![4, 1, 2],
+        vec![4, 2, 3],
+        vec![4, 3, 0],
+    ];
+    from_polygons(vertices, &polygons)
+}
+
+pub fn cuboid(size: Vector3) -> Mesh {
+    //! A box with the given width, height and depth.
+    let half = size / 2.0;
+    let vertices = (0..8)
+        .map(|i| {
+            vec3!(
+                if i & 1 == 0 { -half.x } else { half.x },
+                if i & 2 == 0 { -half.y } else { half.y },
+                if i & 4 == 0 { -half.z } else { half.z }
+            )
+        })
+        .collect();
+    let polygons = [
+        vec![0, 2, 3, 1],
+        vec![4, 5, 7, 6],
+        vec![0, 1, 5, 4],
+        vec![2, 6, 7, 3],
+        vec![0, 4, 6, 2],
+        vec![1, 3, 7, 5],
+    ];
+    from_polygons(vertices, &polygons)
+}
+
+pub fn tetrahedron(radius: f32) -> Mesh {
+    //! A regular tetrahedron (four triangles), with every corner radius from the center.
+    let scale = radius / 3f32.sqrt();
+    let vertices: Vec<Vector3> = [
+        vec3!(1.0, 1.0, 1.0),
+        vec3!(1.0, -1.0, -1.0),
+        vec3!(-1.0, 1.0, -1.0),
+        vec3!(-1.0, -1.0, 1.0),
+    ]
+    .into_iter()
+    .map(|vertex| vertex * scale)
+    .collect();
+    let polygons = triangles_with_side(&vertices, 2.0 * 2f32.sqrt() * scale);
+    from_polygons(vertices, &polygons)
+}
+
+pub fn cube(radius: f32) -> Mesh {
+    //! A cube with every corner radius from the center, to match the other regular polyhedra. Use ```cuboid()``` to give the length of its sides instead.
+    cuboid(vec3!(1.0, 1.0, 1.0) * (2.0 * radius / 3f32.sqrt()))
+}
+
+pub fn octahedron(radius: f32) -> Mesh {
+    //! A regular octahedron (eight triangles), with every corner radius from the center.
+    let vertices = vec![
+        vec3!(radius, 0.0, 0.0),
+        vec3!(-radius, 0.0, 0.0),
+        vec3!(0.0, radius, 0.0),
+        vec3!(0.0, -radius, 0.0),
+        vec3!(0.0, 0.0, radius),
+        vec3!(0.0, 0.0, -radius),
+    ];
+    let polygons = triangles_with_side(&vertices, radius * 2f32.sqrt());
+    from_polygons(vertices, &polygons)
+}
+
+pub fn icosahedron(radius: f32) -> Mesh {
+    //! A regular icosahedron (twenty triangles), with every corner radius from the center.
+    let mut vertices = vec![];
+    for a in [-1.0, 1.0] {
+        for b in [-PHI, PHI] {
+            vertices.push(vec3!(0.0, a, b));
+            vertices.push(vec3!(a, b, 0.0));
+            vertices.push(vec3!(b, 0.0, a));
+        }
+    }
+    let polygons = triangles_with_side(&vertices, 2.0);
+    let scale = radius / vec3!(0.0, 1.0, PHI).len();
+    let vertices = vertices.into_iter().map(|vertex| vertex * scale).collect();
+    from_polygons(vertices, &polygons)
+}
+
+pub fn dodecahedron(radius: f32) -> Mesh {
+    //! A regular dodecahedron (twelve pentagons), with every corner radius from the center.
+    // The dodecahedron has a corner at the center of each of the icosahedron's faces, and a face around each of its corners
+    let icosahedron = icosahedron(1.0);
+    let faces = icosahedron.get_faces();
+    let corners = icosahedron.get_verticies();
+    let centers: Vec<Vector3> = faces
+        .iter()
+        .map(|&(a, b, c)| (corners[&a] + corners[&b] + corners[&c]).normalize() * radius)
+        .collect();
+    let polygons: Vec<Vec<usize>> = (0..12)
+        .map(|corner| {
+            let axis = corners[&corner];
+            let reference = perpendicular_to(axis);
+            let around = axis.cross(reference);
+            let mut pentagon: Vec<usize> = faces
+                .iter()
+                .enumerate()
+                .filter(|(_, &(a, b, c))| corner == a || corner == b || corner == c)
+                .map(|(i, _)| i)
+                .collect();
+            // Sorting by angle around the corner puts the pentagon's corners in order
+            pentagon.sort_by(|&a, &b| {
+                let angle = |i: usize| centers[i].dot(around).atan2(centers[i].dot(reference));
+                angle(a).total_cmp(&angle(b))
+            });
+            outward(&centers, pentagon)
+        })
+        .collect();
+    from_polygons(centers, &polygons)
+}
+
+pub fn arrow(vector: Vector3, head_length: f32, head_radius: f32, segments: usize) -> Mesh {
+    //! An arrow from the origin to vector, drawn as a single line with a cone for a head. The head has segments (at least 3) sides, and is never longer than the arrow itself.
+    let length = vector.len();
+    let direction = vector.normalize();
+    let head_length = head_length.min(length);
+    let segments = segments.max(3);
+    let side = perpendicular_to(direction);
+    let other_side = direction.cross(side);
+    let base = direction * (length - head_length);
+
+    let mut vertices = vec![vec3!(0.0, 0.0, 0.0), vector];
+    for segment in 0..segments {
+        let angle = TAU * segment as f32 / segments as f32;
+        vertices.push(base + (side * angle.cos() + other_side * angle.sin()) * head_radius);
+    }
+    let mut polygons: Vec<Vec<usize>> = (0..segments)
+        .map(|i| vec![1, 2 + i, 2 + (i + 1) % segments])
+        .collect();
+    polygons.push((2..2 + segments).rev().collect());
+    let mut mesh = from_polygons(vertices, &polygons);
+    if head_length < length {
+        mesh.insert_vertex(2 + segments, base);
+        mesh.add_edge((0, 2 + segments));
+    }
+    mesh
+}
+
+pub fn axes(length: f32) -> Vec<Mesh> {
+    //! A gizmo showing the directions of the x, y and z axes, as a red, green and blue arrow of the given length. Keep in mind that +y points down the screen.
+    let head_length = length * 0.2;
+    [
+        (vec3!(length, 0.0, 0.0), Color::Red),
+        (vec3!(0.0, length, 0.0), Color::Green),
+        (vec3!(0.0, 0.0, length), Color::Blue),
+    ]
+    .into_iter()
+    .map(|(vector, color)| {
+        let mut arrow = arrow(vector, head_length, head_length * 0.4, 4);
+        arrow.color = Some(color);
+        arrow
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vec2;
+
+    /// Checks that the mesh is closed up with no holes, which for a shape without any holes through it (unlike a torus) means it has two more vertices than half its triangles, and that every edge is used.
+    fn assert_closed(mesh: &Mesh) {
+        let (vertices, faces) = (mesh.get_verticies().len(), mesh.get_faces().len());
+        assert_eq!(faces, 2 * vertices - 4);
+        let mut counts: HashMap<(usize, usize), usize> = HashMap::new();
+        for &(a, b, c) in mesh.get_faces() {
+            for (a, b) in [(a, b), (b, c), (c, a)] {
+                *counts.entry((a.min(b), a.max(b))).or_default() += 1;
+            }
+        }
+        assert!(counts.values().all(|&count| count == 2));
+        for &(a, b) in mesh.get_edges() {
+            assert_eq!(counts.get(&(a.min(b), a.max(b))), Some(&2));
+        }
+    }
+
+    /// Checks that every face is wound to face outwards, away from the point that inside gives for the middle of the face.
+    fn assert_outward(mesh: &Mesh, inside: impl Fn(Vector3) -> Vector3) {
+        let vertices = mesh.get_verticies();
+        for &(a, b, c) in mesh.get_faces() {
+            let (a, b, c) = (vertices[&a], vertices[&b], vertices[&c]);
+            let middle = (a + b + c) / 3.0;
+            assert!((b - a).cross(c - a).dot(middle - inside(middle)) > 0.0);
+        }
+    }
+
+    /// The average position of the corners of the mesh's faces, which is inside any shape without dents in it.
+    fn centroid(mesh: &Mesh) -> Vector3 {
+        let corners: HashSet<usize> = mesh
+            .get_faces()
+            .iter()
+            .flat_map(|&(a, b, c)| [a, b, c])
+            .collect();
+        corners
+            .iter()
+            .map(|i| mesh.get_verticies()[i])
+            .sum::<Vector3>()
+            / corners.len() as f32
+    }
+
+    fn assert_on_sphere(mesh: &Mesh, radius: f32) {
+        for vertex in mesh.get_verticies().values() {
+            assert!((vertex.len() - radius).abs() < 1e-4, "{vertex:?}");
+        }
+    }
+
+    #[test]
+    fn spheres() {
+        let sphere = uv_sphere(2.0, 8, 4);
+        assert_eq!(sphere.get_verticies().len(), 8 * 3 + 2);
+        // Rings and lines of longitude, but not the diagonals of the quads
+        assert_eq!(sphere.get_edges().len(), 8 * 3 + 8 * 4);
+        assert_closed(&sphere);
+        assert_outward(&sphere, |_| centroid(&sphere));
+        assert_on_sphere(&sphere, 2.0);
+
+        let icosphere = icosphere(1.5, 2);
+        assert_eq!(icosphere.get_faces().len(), 20 * 4 * 4);
+        assert_eq!(icosphere.get_verticies().len(), 162);
+        assert_closed(&icosphere);
+        assert_outward(&icosphere, |_| centroid(&icosphere));
+        assert_on_sphere(&icosphere, 1.5);
+    }
+
+    #[test]
+    fn regular_polyhedra() {
+        for (mesh, corners, edges) in [
+            (tetrahedron(1.0), 4, 6),
+            (cube(1.0), 8, 12),
+            (octahedron(1.0), 6, 12),
+            (dodecahedron(1.0), 20, 30),
+            (icosahedron(1.0), 12, 30),
+        ] {
+            assert_eq!(mesh.get_verticies().len(), corners);
+            assert_eq!(mesh.get_edges().len(), edges);
+            assert_closed(&mesh);
+            assert_outward(&mesh, |_| centroid(&mesh));
+            assert_on_sphere(&mesh, 1.0);
+            let vertices = mesh.get_verticies();
+            let side = |&(a, b): &(usize, usize)| vertices[&a].distance(vertices[&b]);
+            let first = side(&mesh.get_edges()[0]);
+            assert!(mesh
+                .get_edges()
+                .iter()
+                .all(|edge| (side(edge) - first).abs() < 1e-4));
+        }
+    }
+
+    #[test]
+    fn other_shapes() {
+        for mesh in [
+            cylinder(1.0, 2.0, 6),
+            cone(1.0, 2.0, 2),
+            pyramid(2.0, 1.0),
+            cuboid(vec3!(1.0, 2.0, 3.0)),
+        ] {
+            assert_closed(&mesh);
+            assert_outward(&mesh, |_| centroid(&mesh));
+        }
+        assert_eq!(cylinder(1.0, 2.0, 6).get_edges().len(), 18);
+        assert_eq!(cone(1.0, 2.0, 2).get_verticies().len(), 4);
+        assert_eq!(pyramid(2.0, 1.0).get_verticies()[&4], vec3!(0.0, -0.5, 0.0));
+
+        let torus = torus(2.0, 0.5, 12, 6);
+        assert_eq!(torus.get_verticies().len(), 72);
+        assert_eq!(torus.get_faces().len(), 2 * 72);
+        assert_eq!(torus.get_edges().len(), 2 * 72);
+        // The inside of a torus is the circle through the middle of its tube
+        assert_outward(&torus, |point| on_circle(2.0, point.z.atan2(point.x), 0.0));
+
+        let grid = grid(vec2!(4.0, 2.0), (4, 2));
+        assert_eq!(grid.get_verticies().len(), 15);
+        assert_eq!(grid.get_edges().len(), 4 * 3 + 5 * 2);
+        assert_eq!(grid.get_verticies()[&14], vec3!(2.0, 0.0, 1.0));
+        // Flat shapes face up the screen
+        assert_outward(&grid, |point| point + vec3!(0.0, 1.0, 0.0));
+        let plane = plane(vec2!(1.0, 1.0));
+        assert_eq!(plane.get_faces().len(), 2);
+        assert_outward(&plane, |point| point + vec3!(0.0, 1.0, 0.0));
+
+        let arrow = arrow(vec3!(0.0, 0.0, 5.0), 1.0, 0.25, 4);
+        assert_eq!(arrow.get_verticies()[&1], vec3!(0.0, 0.0, 5.0));
+        assert_eq!(arrow.get_verticies()[&6], vec3!(0.0, 0.0, 4.0));
+        assert!(arrow.get_edges().contains(&(0, 6)));
+        let axes = axes(1.0);
+        assert_eq!(axes.len(), 3);
+        assert_eq!(axes[1].color, Some(Color::Green));
+        for arrow in axes.iter().chain([&arrow]) {
+            assert_outward(arrow, |_| centroid(arrow));
+        }
+    }
+}
@@ -1,6 +1,12 @@
-use super::char_buffer::CharBuffer;
-use super::line::Line;
+use super::canvas::Canvas;
+use super::depth_buffer::DepthBuffer;
+use super::feature_edges::FeatureEdges;
+use super::line::{Line, Stroke};
+use super::scene::Scene;
+use super::triangle::Triangle;
+use crossterm::style::Color;
 use std::collections::HashMap;
+use std::ops::Range;
 
 /// Slightly more concise way of declaring a Vector3
 #[macro_export]
@@ -24,112 +30,610 @@ macro_rules! vec2 {
     };
 }
 
-/// Used for rendering meshs to a CharBuffer.
+/// Used for rendering meshs to a CharBuffer, or any other Canvas.
+/// If ```depth_test``` is true, a depth buffer is kept alongside the canvas while drawing so that edges closer to the camera are drawn over edges further away, across all of the meshs.
+/// Meshs can either be placed in the world directly through ```meshs```, or attached to nodes of the ```scene``` so that they move along with their parents. Both are drawn, followed by the ```labels``` on top of them.
+/// If ```feature_edges``` is set, wireframes only show the edges that outline each mesh's shape (see ```FeatureEdges```) rather than every edge, which keeps dense meshs readable.
+/// Create it with ```Renderer::new()``` and change the fields afterwards, rather than with a struct literal, which breaks whenever a field is added.
 #[derive(Debug, Clone)]
 pub struct Renderer {
     pub meshs: Vec<Mesh>,
+    pub scene: Scene,
+    pub labels: Vec<Label>,
     pub camera: Camera,
+    pub depth_test: bool,
+    pub mode: RenderMode,
+    pub feature_edges: Option<FeatureEdges>,
+}
+
+/// The position of each vertex of a mesh in global and view space, by vertex index.
+type TransformedVertices = HashMap<usize, (Vector3, Vector3)>;
+
+/// Determines how the Renderer draws meshs.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RenderMode {
+    /// Every edge is drawn as a line using the mesh's char.
+    Wireframe,
+    /// The faces of each mesh are filled, with each cell's char picked from the shading's ramp based on how directly the face points towards the light. Always depth tested. Meshs without faces are still drawn as wireframes.
+    Shaded(Shading),
+}
+
+/// The settings used by ```RenderMode::Shaded```.
+/// The ramp goes from darkest to brightest, and the light direction points from the meshs towards the light.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Shading {
+    pub ramp: Vec<char>,
+    pub light_direction: Vector3,
+}
+
+impl Shading {
+    pub fn new(ramp: &str, light_direction: Vector3) -> Self {
+        Self {
+            ramp: ramp.chars().collect(),
+            light_direction,
+        }
+    }
+    /// Picks the char from the ramp for a face with the given normal. Faces pointing straight at the light get the last char, faces pointing away from it get the first.
+    pub fn char_for(&self, normal: Vector3) -> char {
+        if self.ramp.is_empty() {
+            return ' ';
+        }
+        let brightness = normal
+            .normalize()
+            .dot(self.light_direction.normalize())
+            .clamp(0.0, 1.0);
+        self.ramp[(brightness * (self.ramp.len() - 1) as f32).round() as usize]
+    }
+}
+
+impl std::default::Default for Shading {
+    fn default() -> Self {
+        //! A nine step ramp lit from above and in front of the default camera. The darkest step is a dot rather than a space, so that faces turned away from the light are still drawn.
+        Self::new(".:-=+*#%@", vec3!(0.0, -1.0, -1.0))
+    }
 }
 
 impl Renderer {
-    ///Draws all the meshs to the CharBuffer
+    /// Creates a wireframe renderer with depth testing disabled.
+    pub fn new(meshs: Vec<Mesh>, camera: Camera) -> Self {
+        Self {
+            meshs,
+            scene: Scene::new(),
+            labels: vec![],
+            camera,
+            depth_test: false,
+            mode: RenderMode::Wireframe,
+            feature_edges: None,
+        }
+    }
+    ///Draws all the meshs to the canvas
     /// # Example
     /// ```
-    /// let buf = CharBuffer::new(30, 30);  //Make sure to use a char buffer that has dimensions proportional to the camera's FOV, otherwise everything will be stretched oddly...
-    /// let renderer = Renderer {
-    ///     meshs: vec![create_cube()],
-    ///     camera: Camera {
-    ///         position: vec3!(0.0, 0.0, -10.0),
-    ///         rotation: vec3!(0.0, 0.0, 0.0),
-    ///         fov: vec2!(0.7, 0.7);   //FOV is in radians
-    ///     },
-    /// };
+    /// # use ascii_renderer::prelude::*;
+    /// let mut buf = CharBuffer::new(30, 30);  //Make sure to use a char buffer that has dimensions proportional to the camera's FOV (or set the camera's auto_aspect), otherwise everything will be stretched oddly...
+    /// let renderer = Renderer::new(
+    ///     vec![ascii_renderer::create_cube()],
+    ///     Camera::new(
+    ///         vec3!(0.0, 0.0, -10.0), //Position
+    ///         vec3!(0.0, 0.0, 0.0),   //Rotation
+    ///         vec2!(0.7, 0.7),        //FOV, in radians
+    ///     ),
+    /// );
     /// renderer.draw(&mut buf);
     /// println!("{buf}");
     /// ```
-    pub fn draw(&self, buffer: &mut CharBuffer) {
-        for mesh in self.meshs.iter() {
-            self.draw_mesh(mesh, buffer);
+    pub fn draw<C: Canvas>(&self, buffer: &mut C) {
+        let meshs = self
+            .meshs
+            .iter()
+            .map(|mesh| (mesh, mesh.model_matrix()))
+            .chain(self.scene.meshs());
+        // Shared by every mesh, so that it is only allocated once per frame
+        let mut vertices = TransformedVertices::new();
+        if let RenderMode::Shaded(shading) = &self.mode {
+            let mut depth_buffer = DepthBuffer::new(buffer.dimensions().0, buffer.dimensions().1);
+            for (mesh, model) in meshs {
+                self.draw_shaded(
+                    mesh,
+                    &model,
+                    shading,
+                    buffer,
+                    &mut depth_buffer,
+                    &mut vertices,
+                );
+            }
+        } else if self.depth_test {
+            let mut depth_buffer = DepthBuffer::new(buffer.dimensions().0, buffer.dimensions().1);
+            for (mesh, model) in meshs {
+                self.draw_depth_tested(mesh, &model, buffer, &mut depth_buffer, &mut vertices);
+            }
+        } else {
+            for (mesh, model) in meshs {
+                self.draw_wireframe(mesh, &model, buffer, &mut vertices);
+            }
+        }
+        for label in self.labels.iter() {
+            self.draw_label(label, buffer);
         }
     }
+    /// Writes a label's text centered on the point its position maps to, over whatever is already there. Labels behind the camera aren't drawn, and any chars that don't fit on the canvas are cut off.
+    pub fn draw_label<C: Canvas>(&self, label: &Label, buffer: &mut C) {
+        let dimensions = buffer.dimensions();
+        let view = self.camera.to_view_space(label.position);
+        if view.z < self.camera.near {
+            return;
+        }
+        let point = view_to_screen(&self.camera.projection_matrix(dimensions), view, dimensions);
+        let start = point.x.floor() as i64 - label.text.chars().count() as i64 / 2;
+        let y = point.y.floor();
+        if y < 0.0 || y >= dimensions.1 as f32 {
+            return;
+        }
+        buffer.set_color(label.color);
+        for (i, char) in label.text.chars().enumerate() {
+            let x = start + i as i64;
+            if x >= 0 && x < dimensions.0 as i64 {
+                buffer.plot(x as usize, y as usize, char);
+            }
+        }
+        buffer.set_color(None);
+    }
     /// Draws an individual mesh.
-    pub fn draw_mesh(&self, mesh: &Mesh, buffer: &mut CharBuffer) {
-        let point_map: HashMap<usize, (Vector2, bool)> = mesh
-            .get_global_verticies()
-            .iter()
-            .map(|(&k, &v)| {
-                let mut pnt = self.camera.map_point_uv(v);
-                pnt.x *= buffer.dimensions.0 as f32;
-                pnt.y *= buffer.dimensions.1 as f32;
-                (k, (pnt, self.camera.is_pnt_on_screen(v)))
-            })
-            .fold(HashMap::new(), |mut accum, (k, v)| {
-                accum.insert(k, v);
-                accum
-            });
+    pub fn draw_mesh<C: Canvas>(&self, mesh: &Mesh, buffer: &mut C) {
+        self.draw_wireframe(
+            mesh,
+            &mesh.model_matrix(),
+            buffer,
+            &mut TransformedVertices::new(),
+        );
+    }
+    /// Draws an individual mesh, only writing chars that are closer than what the depth buffer already holds. Sharing one depth buffer between several calls lets meshs occlude each other.
+    pub fn draw_mesh_depth_tested<C: Canvas>(
+        &self,
+        mesh: &Mesh,
+        buffer: &mut C,
+        depth_buffer: &mut DepthBuffer,
+    ) {
+        self.draw_depth_tested(
+            mesh,
+            &mesh.model_matrix(),
+            buffer,
+            depth_buffer,
+            &mut TransformedVertices::new(),
+        );
+    }
+    /// Fills the faces of an individual mesh using the shading's ramp. Meshs without faces are drawn as depth tested wireframes instead.
+    pub fn draw_mesh_shaded<C: Canvas>(
+        &self,
+        mesh: &Mesh,
+        shading: &Shading,
+        buffer: &mut C,
+        depth_buffer: &mut DepthBuffer,
+    ) {
+        self.draw_shaded(
+            mesh,
+            &mesh.model_matrix(),
+            shading,
+            buffer,
+            depth_buffer,
+            &mut TransformedVertices::new(),
+        );
+    }
+    /// Draws a mesh whose vertices are taken to the world by the given model matrix, rather than by the mesh's own. Vertices is only used to hold the mesh's transformed vertices, so that drawing several meshs can reuse the same map.
+    fn draw_wireframe<C: Canvas>(
+        &self,
+        mesh: &Mesh,
+        model: &Matrix4,
+        buffer: &mut C,
+        vertices: &mut TransformedVertices,
+    ) {
+        buffer.set_color(mesh.color);
+        let lines: Vec<Line> = self
+            .project_edges(mesh, model, buffer.dimensions(), vertices)
+            .into_iter()
+            .map(|(line, _)| line)
+            .collect();
+
+        buffer.draw_lines(lines);
+        buffer.set_color(None);
+    }
+    fn draw_depth_tested<C: Canvas>(
+        &self,
+        mesh: &Mesh,
+        model: &Matrix4,
+        buffer: &mut C,
+        depth_buffer: &mut DepthBuffer,
+        vertices: &mut TransformedVertices,
+    ) {
+        buffer.set_color(mesh.color);
+        for (line, depths) in self.project_edges(mesh, model, buffer.dimensions(), vertices) {
+            buffer.draw_line_depth_tested(line, depths, depth_buffer);
+        }
+        buffer.set_color(None);
+    }
+    fn draw_shaded<C: Canvas>(
+        &self,
+        mesh: &Mesh,
+        model: &Matrix4,
+        shading: &Shading,
+        buffer: &mut C,
+        depth_buffer: &mut DepthBuffer,
+        vertices: &mut TransformedVertices,
+    ) {
+        if mesh.faces.is_empty() {
+            self.draw_depth_tested(mesh, model, buffer, depth_buffer, vertices);
+            return;
+        }
+
+        buffer.set_color(mesh.color);
+        let dimensions = buffer.dimensions();
+        let projection = self.camera.projection_matrix(dimensions);
+        let view = self.camera.view_matrix();
+        self.transform_vertices(mesh, model, vertices);
+
+        for &(a, b, c) in mesh.faces.iter() {
+            let (global_a, view_a) = vertices[&a];
+            let (global_b, view_b) = vertices[&b];
+            let (global_c, view_c) = vertices[&c];
+            let mut normal = (global_b - global_a).cross(global_c - global_a);
+            let view_direction = match self.camera.projection {
+                Projection::Perspective => view_a,
+                Projection::Orthographic { .. } => vec3!(0.0, 0.0, 1.0),
+            };
+            if view.transform_vector(normal).dot(view_direction) > 0.0 {
+                normal = -normal; //Faces are lit from whichever side is facing the camera
+            }
+            let char = shading.char_for(normal);
 
-        let lines: Vec<Line> = mesh
-            .edges
+            let clipped = self.camera.clip_view_polygon(&[view_a, view_b, view_c]);
+            //Clipping can turn the triangle into a quad, which gets split back into triangles as a fan
+            for i in 1..clipped.len().saturating_sub(1) {
+                let (view_a, view_b, view_c) = (clipped[0], clipped[i], clipped[i + 1]);
+                buffer.draw_triangle_depth_tested(
+                    Triangle {
+                        char,
+                        points: (
+                            view_to_screen(&projection, view_a, dimensions),
+                            view_to_screen(&projection, view_b, dimensions),
+                            view_to_screen(&projection, view_c, dimensions),
+                        ),
+                        depths: (view_a.z, view_b.z, view_c.z),
+                    },
+                    depth_buffer,
+                );
+            }
+        }
+        buffer.set_color(None);
+    }
+    /// Moves every vertex of the mesh into global (using the given model matrix) and view space, replacing whatever vertices held.
+    fn transform_vertices(&self, mesh: &Mesh, model: &Matrix4, vertices: &mut TransformedVertices) {
+        let view = self.camera.view_matrix();
+        vertices.clear();
+        vertices.extend(mesh.vertices.iter().map(|(&index, &vertex)| {
+            let global = model.transform_point(vertex);
+            (index, (global, view.transform_point(global)))
+        }));
+    }
+    /// Projects every edge of the mesh (or only its feature edges, if the renderer's ```feature_edges``` is set), placed in the world by the given model matrix, onto a buffer of the given dimensions, returning the lines along with the depth of each of their ends. Edges are clipped to the camera's near plane before being projected.
+    fn project_edges(
+        &self,
+        mesh: &Mesh,
+        model: &Matrix4,
+        dimensions: (usize, usize),
+        vertices: &mut TransformedVertices,
+    ) -> Vec<(Line, (f32, f32))> {
+        let projection = self.camera.projection_matrix(dimensions);
+        self.transform_vertices(mesh, model, vertices);
+        let features;
+        let edges = match &self.feature_edges {
+            Some(settings) => {
+                features = settings.find(
+                    mesh,
+                    |index| vertices.get(&index).map(|&(_, view)| view),
+                    Some(self.camera.projection),
+                );
+                &features
+            }
+            None => &mesh.edges,
+        };
+
+        edges
             .iter()
             .filter_map(|&point_indexs| {
-                let ((point1, is_on_screen1), (point2, is_on_screen2)) = (*point_map.get(&point_indexs.0).unwrap(), *point_map.get(&point_indexs.1).unwrap());
-
-                if !is_on_screen1 && !is_on_screen2 {return None;}
+                let (start, end) = self.camera.clip_view_edge((
+                    vertices.get(&point_indexs.0).unwrap().1,
+                    vertices.get(&point_indexs.1).unwrap().1,
+                ))?;
 
-                Some(Line {
-                    char: mesh.char,
-                    points: (
-                        (point1).into(),
-                        (point2).into(),
-                    ),
-                })
+                Some((
+                    Line {
+                        stroke: mesh.stroke,
+                        points: (
+                            view_to_screen(&projection, start, dimensions),
+                            view_to_screen(&projection, end, dimensions),
+                        ),
+                    },
+                    (start.z, end.z),
+                ))
             })
-            .collect();
+            .collect()
+    }
+}
 
-        buffer.draw_lines(lines);
+/// Maps a view space point to a point on a buffer of the given dimensions, using the camera's projection for that buffer.
+#[inline]
+fn view_to_screen(projection: &Matrix4, point: Vector3, dimensions: (usize, usize)) -> Vector2 {
+    let projected = projection.transform_point(point);
+    vec2!(
+        (projected.x * 0.5 + 0.5) * dimensions.0 as f32,
+        (projected.y * 0.5 + 0.5) * dimensions.1 as f32
+    )
+}
+
+/// Text placed in the world, such as the numbers along a plot's axes. Labels always face the camera and stay the same size however far away they are.
+/// Each char of the text takes up a single point of the canvas, so labels are best drawn to canvases that show chars, like a CharBuffer or StyledBuffer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Label {
+    pub position: Vector3,
+    pub text: String,
+    pub color: Option<Color>,
+}
+
+impl Label {
+    pub fn new(position: Vector3, text: &str) -> Self {
+        Self {
+            position,
+            text: text.to_owned(),
+            color: None,
+        }
     }
 }
 
+/// The point of view that meshs are rendered from. Anything closer to the camera than ```near``` (measured along the direction the camera faces) is clipped away rather than drawn.
+/// By default meshs are projected with a perspective projection, where ```fov``` is the full angle across and down the view. Both angles must be less than pi. See ```Projection``` for the alternative.
+/// The perspective projection is rectilinear, so straight edges stay straight on screen. Before 2.0 each axis was mapped by its angle from the center instead. The edges of the view are where they were, but everything inside them is now drawn closer to the middle, so the same fov makes things look a little smaller (about 9% at the center for an fov of 1.0). Narrowing the fov gets back roughly the old framing. For the same reason, ```auto_aspect``` now widens the horizontal fov so that its tangent, rather than the angle itself, grows with the buffer's width.
+/// Create it with one of the constructors below rather than a struct literal, which breaks whenever a field is added.
+/// If ```auto_aspect``` is true, only the vertical fov (or height, for an orthographic camera) is used, and the horizontal one is worked out from the shape of whatever is being drawn to, so that meshs aren't stretched when the buffer is resized.
+/// That takes ```cell_aspect``` into account, which is how wide each point of the buffer is compared to how tall it is. It defaults to 1.0, which suits a padded CharBuffer (or a PixelBuffer) in most fonts. An unpadded CharBuffer needs about 0.5, since terminal cells are usually twice as tall as they are wide, and a BrailleCanvas copied onto a padded CharBuffer needs 2.0.
 #[derive(Debug, Clone)]
 pub struct Camera {
     pub position: Vector3,
     pub rotation: Vector3,
     pub fov: Vector2,
+    pub near: f32,
+    pub auto_aspect: bool,
+    pub cell_aspect: f32,
+    pub projection: Projection,
+}
+
+/// How a camera maps what it sees onto the screen.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Projection {
+    /// Things further away look smaller, and the view covers the camera's ```fov```.
+    Perspective,
+    /// Things look the same size however far away they are, and the view is a box of the given width and height in world units, centered on the camera. Good for CAD-style and isometric views.
+    Orthographic { size: Vector2 },
 }
 
 impl Camera {
+    /// Creates a camera with a near plane of 0.1.
+    pub fn new(position: Vector3, rotation: Vector3, fov: Vector2) -> Self {
+        Self {
+            position,
+            rotation,
+            fov,
+            near: 0.1,
+            auto_aspect: false,
+            cell_aspect: 1.0,
+            projection: Projection::Perspective,
+        }
+    }
+
+    /// Creates an orthographic camera that sees a view of the given width and height in world units. Its fov is unused.
+    pub fn new_orthographic(position: Vector3, rotation: Vector3, size: Vector2) -> Self {
+        Self {
+            projection: Projection::Orthographic { size },
+            ..Self::new(position, rotation, vec2!(1.0, 1.0))
+        }
+    }
+
+    /// Creates a camera with auto_aspect set, meaning fov is the vertical fov and the horizontal fov is worked out from whatever is being drawn to.
+    pub fn new_auto_aspect(position: Vector3, rotation: Vector3, fov: f32) -> Self {
+        Self {
+            auto_aspect: true,
+            ..Self::new(position, rotation, vec2!(fov, fov))
+        }
+    }
+
+    /// Turns the camera to face the target, with up being whichever way should point up the screen. The screen's y grows downwards, so a camera with no rotation has -y as its up, which is also what suits meshs loaded from OBJ files after flipping them (as in the OBJ example); meshs that are meant to be +y up can be viewed without flipping by passing +y instead.
+    /// If up and the direction to the target are parallel, any roll is picked. Targets at the camera's position leave the rotation as it is.
+    /// # Example
+    /// ```
+    /// # use ascii_renderer::prelude::*;
+    /// let mut camera = Camera::new_auto_aspect(vec3!(5.0, -5.0, 5.0), vec3!(0.0, 0.0, 0.0), 1.0);
+    /// camera.look_at(vec3!(0.0, 0.0, 0.0), vec3!(0.0, -1.0, 0.0));
+    /// assert!(camera.to_view_space(vec3!(0.0, 0.0, 0.0)).approx_eq(vec3!(0.0, 0.0, 75f32.sqrt()), 0.001));
+    /// ```
+    pub fn look_at(&mut self, target: Vector3, up: Vector3) {
+        let forward = (target - self.position).normalize();
+        if forward.len() == 0.0 {
+            return;
+        }
+        let mut right = forward.cross(up);
+        if right.len() < 1e-6 {
+            right = forward.cross(perpendicular_to(forward));
+        }
+        let right = right.normalize();
+        let down = forward.cross(right);
+
+        // The rows of the view rotation are right, down and forward, which are pulled apart into the angles of Rz * Ry * Rx
+        let horizontal = (forward.y * forward.y + forward.z * forward.z).sqrt();
+        self.rotation = if horizontal < 1e-6 {
+            // Facing along x, where the x and z angles do the same thing, so x is left at 0.0
+            vec3!(0.0, forward.x.atan2(horizontal), (-right.y).atan2(down.y))
+        } else {
+            vec3!(
+                (-forward.y).atan2(forward.z),
+                forward.x.atan2(horizontal),
+                down.x.atan2(right.x)
+            )
+        };
+    }
+
+    /// Gets the camera that is actually used for drawing to a buffer of the given dimensions. That is just this camera, unless ```auto_aspect``` is true, in which case the horizontal fov (or width) is replaced with one that matches the buffer's aspect ratio.
+    pub fn fitted(&self, dimensions: (usize, usize)) -> Camera {
+        let mut camera = self.clone();
+        if self.auto_aspect && dimensions.1 > 0 {
+            let aspect = dimensions.0 as f32 * self.cell_aspect / dimensions.1 as f32;
+            camera.fov.x = 2.0 * ((self.fov.y / 2.0).tan() * aspect).atan();
+            if let Projection::Orthographic { size } = &mut camera.projection {
+                size.x = size.y * aspect;
+            }
+        }
+        camera
+    }
+
+    /// The matrix that moves global points into the camera's space (see ```to_view_space()```).
+    pub fn view_matrix(&self) -> Matrix4 {
+        Matrix4::rotation(self.rotation) * Matrix4::translation(-self.position)
+    }
+
+    /// The projection used when drawing to a buffer of the given dimensions, which takes view space points to x and y between -1.0 and 1.0 across the buffer. See ```Matrix4::perspective()``` and ```Matrix4::orthographic()```.
+    pub fn projection_matrix(&self, dimensions: (usize, usize)) -> Matrix4 {
+        self.fitted(dimensions).own_projection_matrix()
+    }
+
+    /// The projection made from the camera's own fov (or size), without fitting it to a buffer.
+    fn own_projection_matrix(&self) -> Matrix4 {
+        match self.projection {
+            Projection::Perspective => Matrix4::perspective(self.fov, self.near),
+            Projection::Orthographic { size } => Matrix4::orthographic(size),
+        }
+    }
+
     /// Maps a global 3d point to the screen. The output is a UV point, meaning the top left of the screen is (0.0, 0.0) and the bottom right is (1.0, 1.0).
     pub fn map_point_uv(&self, point: Vector3) -> Vector2 {
-        // Maps a three dimensional GLOBAL point to UV point dictating its location on screen
+        self.map_view_point_uv(self.to_view_space(point))
+    }
+
+    /// Maps a point that is already relative to the camera (see ```to_view_space()```) to a UV point on the screen. Only points in front of the camera map to anything meaningful.
+    pub fn map_view_point_uv(&self, relative: Vector3) -> Vector2 {
         //EX: (0.0, 0.0) is top left of screen and (1.0, 1.0) is bottom right of screen
-        let relative = (point - self.position).rotate(self.rotation);
+        match self.projection {
+            Projection::Perspective => vec2!(
+                relative.x / (relative.z * (self.fov.x / 2.0).tan()) * 0.5 + 0.5,
+                relative.y / (relative.z * (self.fov.y / 2.0).tan()) * 0.5 + 0.5
+            ),
+            Projection::Orthographic { size } => {
+                vec2!(relative.x / size.x + 0.5, relative.y / size.y + 0.5)
+            }
+        }
+    }
 
-        let thetas = vec2!(
-            vec2!(relative.z, relative.x).to_polar().y,
-            vec2!(relative.z, relative.y).to_polar().y
-        );
+    /// Moves a global 3d point into the camera's space, where the camera is at the origin looking down the positive z axis.
+    pub fn to_view_space(&self, point: Vector3) -> Vector3 {
+        (point - self.position).rotate(self.rotation)
+    }
 
-        vec2!(thetas.x / self.fov.x + 0.5, thetas.y / self.fov.y + 0.5)
+    /// Gets the depth of a global 3d point, which is its distance along the direction the camera is facing. Points behind the camera have a negative depth.
+    pub fn depth_of(&self, point: Vector3) -> f32 {
+        self.to_view_space(point).z
     }
 
-    /// Checks if a point is on screen
+    /// Checks if a point is on screen, going by the camera's own fov (or size). For an ```auto_aspect``` camera, whose horizontal fov depends on what it draws to, use ```is_pnt_on_buffer()``` instead.
     pub fn is_pnt_on_screen(&self, point: Vector3) -> bool {
-        let relative = (point - self.position).rotate(self.rotation);
-        
-        !(vec2!(relative.z, relative.x).to_polar().y.abs() > self.fov.x / 2.0) && !(vec2!(relative.z, relative.y).to_polar().y.abs() > self.fov.y / 2.0)
+        self.is_pnt_in_projection(point, &self.own_projection_matrix())
+    }
+
+    /// Checks if a point is on screen when drawing to a buffer of the given dimensions, using the same projection as the renderer (see ```projection_matrix()```).
+    pub fn is_pnt_on_buffer(&self, point: Vector3, dimensions: (usize, usize)) -> bool {
+        self.is_pnt_in_projection(point, &self.projection_matrix(dimensions))
+    }
+
+    fn is_pnt_in_projection(&self, point: Vector3, projection: &Matrix4) -> bool {
+        let relative = self.to_view_space(point);
+
+        if relative.z < self.near {
+            return false;
+        }
+        let projected = projection.transform_point(relative);
+        projected.x.abs() <= 1.0 && projected.y.abs() <= 1.0
+    }
+
+    /// Clips an edge (in view space) to the part of it that is in front of the near plane. Returns None if the whole edge is behind it.
+    pub fn clip_view_edge(&self, edge: (Vector3, Vector3)) -> Option<(Vector3, Vector3)> {
+        let (mut start, mut end) = edge;
+        if start.z < self.near && end.z < self.near {
+            return None;
+        }
+        if start.z < self.near {
+            start = intersect_near(end, start, self.near);
+        } else if end.z < self.near {
+            end = intersect_near(start, end, self.near);
+        }
+        Some((start, end))
     }
+
+    /// Clips a polygon (in view space) to the part of it that is in front of the near plane. A clipped triangle can gain a corner, so the result may have one more point than the input, or none at all.
+    pub fn clip_view_polygon(&self, polygon: &[Vector3]) -> Vec<Vector3> {
+        let mut ret = vec![];
+        for (i, &current) in polygon.iter().enumerate() {
+            let next = polygon[(i + 1) % polygon.len()];
+            let (current_inside, next_inside) = (current.z >= self.near, next.z >= self.near);
+            if current_inside {
+                ret.push(current);
+            }
+            if current_inside != next_inside {
+                ret.push(if current_inside {
+                    intersect_near(current, next, self.near)
+                } else {
+                    intersect_near(next, current, self.near)
+                });
+            }
+        }
+        ret
+    }
+}
+
+/// Gets the point where the segment from ```inside``` to ```outside``` crosses the near plane.
+#[inline]
+fn intersect_near(inside: Vector3, outside: Vector3, near: f32) -> Vector3 {
+    let t = (inside.z - near) / (inside.z - outside.z);
+    inside + (outside - inside) * t
+}
+
+/// Gets a unit vector at right angles to the given (non-zero) vector. Which one is unspecified, but it is always the same for the same input.
+#[inline]
+pub(crate) fn perpendicular_to(vector: Vector3) -> Vector3 {
+    let other = if vector.normalize().z.abs() < 0.9 {
+        vec3!(0.0, 0.0, 1.0)
+    } else {
+        vec3!(1.0, 0.0, 0.0)
+    };
+    vector.cross(other).normalize()
 }
 
 /// A struct containing all the data for a mesh. Rotation, as with everything in this crate, is in radians, with each value determining the amount that the mesh should be rotated around the given axis.
 /// Note that vertices are stored on a hashmap, not a vector.
+/// Faces are optional triangles made of three vertex indices, and are only used by ```RenderMode::Shaded```.
+/// Normals and UVs (texture coordinates) are also optional, and are stored per vertex index alongside the vertices. Meshs loaded from OBJ files have whatever the file gives, the name of the object the mesh came from, and which of its faces belong to each of the object's groups.
+/// The color is used by canvases that support color, like a PixelBuffer. A mesh without one uses whatever color the canvas is already drawing with.
+/// The stroke determines how edges are drawn, either with a single char (```Stroke::Char('+')``` by default) or with glyphs picked from each edge's slope (```Stroke::Slope```).
 #[derive(Debug, Clone)]
 pub struct Mesh {
     vertices: HashMap<usize, Vector3>,
     edges: Vec<(usize, usize)>,
+    faces: Vec<(usize, usize, usize)>,
+    normals: HashMap<usize, Vector3>,
+    uvs: HashMap<usize, Vector2>,
     pub rotation: Vector3,
     pub position: Vector3,
     pub scale: Vector3,
-    pub char: char,
+    pub stroke: Stroke,
+    pub color: Option<Color>,
+    pub object: Option<String>,
+    ///The name of every group the faces were loaded from, along with the range of indices into the faces that belong to it.
+    pub groups: Vec<(String, Range<usize>)>,
 }
 
 impl Mesh {
@@ -137,7 +641,7 @@ impl Mesh {
         self.vertices.insert(index, vertex)
     }
     pub fn get_vertex(&mut self, index: usize) -> Option<Vector3> {
-        self.vertices.get(&index).map(|&x| x)
+        self.vertices.get(&index).copied()
     }
     pub fn insert_vertices(
         &mut self,
@@ -149,6 +653,9 @@ impl Mesh {
             .collect()
     }
     pub fn remove_vertex(&mut self, index: usize) -> Option<Vector3> {
+        //! Removes the vertex along with its normal and UV, if it has them. Edges and faces using it are left as they are.
+        self.normals.remove(&index);
+        self.uvs.remove(&index);
         self.vertices.remove(&index)
     }
     pub fn get_verticies(&self) -> &HashMap<usize, Vector3> {
@@ -175,23 +682,100 @@ impl Mesh {
     pub fn get_edges_mut(&mut self) -> &mut Vec<(usize, usize)> {
         &mut self.edges
     }
+    pub fn add_face(&mut self, face: (usize, usize, usize)) {
+        self.faces.push(face)
+    }
+    pub fn add_faces(&mut self, faces: Vec<(usize, usize, usize)>) {
+        for face in faces {
+            self.faces.push(face);
+        }
+    }
+    pub fn remove_face(&mut self, face: (usize, usize, usize)) -> Option<(usize, usize, usize)> {
+        let i = self.faces.iter().enumerate().find(|(_, &x)| x == face)?.0;
+        Some(self.faces.remove(i))
+    }
+    pub fn get_faces(&self) -> &Vec<(usize, usize, usize)> {
+        &self.faces
+    }
+    pub fn get_faces_mut(&mut self) -> &mut Vec<(usize, usize, usize)> {
+        &mut self.faces
+    }
+    pub fn get_group_faces(&self, group: &str) -> Option<&[(usize, usize, usize)]> {
+        //! Gets the faces of the first group with the given name, if the mesh has one.
+        let (_, range) = self.groups.iter().find(|(name, _)| name == group)?;
+        self.faces.get(range.clone())
+    }
+    pub fn get_normals(&self) -> &HashMap<usize, Vector3> {
+        &self.normals
+    }
+    pub fn get_normals_mut(&mut self) -> &mut HashMap<usize, Vector3> {
+        &mut self.normals
+    }
+    pub fn get_uvs(&self) -> &HashMap<usize, Vector2> {
+        &self.uvs
+    }
+    pub fn get_uvs_mut(&mut self) -> &mut HashMap<usize, Vector2> {
+        &mut self.uvs
+    }
+    pub fn feature_edges(
+        &self,
+        features: &FeatureEdges,
+        camera: Option<&Camera>,
+    ) -> Vec<(usize, usize)> {
+        //! Finds the edges that outline the mesh's shape, as described by ```FeatureEdges```. Silhouettes depend on where the mesh is seen from, so they are only found if a camera is given, with the mesh placed by its own position, rotation and scale.
+        //! Setting the mesh's edges to the boundaries and creases bakes them in, for drawing a dense mesh that doesn't change without finding them every frame.
+        match camera {
+            Some(camera) => {
+                let matrix = camera.view_matrix() * self.model_matrix();
+                features.find(
+                    self,
+                    |index| {
+                        self.vertices
+                            .get(&index)
+                            .map(|&vertex| matrix.transform_point(vertex))
+                    },
+                    Some(camera.projection),
+                )
+            }
+            None => features.find(self, |index| self.vertices.get(&index).copied(), None),
+        }
+    }
     pub fn get_global_verticies(&self) -> HashMap<usize, Vector3> {
-        let mut ret = self.vertices.clone();
-        ret.iter_mut().for_each(|(_, item)| {
-            item.x *= self.scale.x;
-            item.y *= self.scale.y;
-            item.z *= self.scale.z;
-
-            *item = item.rotate(self.rotation);
-
-            *item = *item + self.position;
-        });
-        ret
+        let model = self.model_matrix();
+        self.vertices
+            .iter()
+            .map(|(&index, &vertex)| (index, model.transform_point(vertex)))
+            .collect()
+    }
+    /// The matrix that takes vertices to their global position, scaling, then rotating, then moving them.
+    pub fn model_matrix(&self) -> Matrix4 {
+        Matrix4::translation(self.position)
+            * Matrix4::rotation(self.rotation)
+            * Matrix4::scale(self.scale)
+    }
+    /// The mesh's position, rotation and scale as a Transform.
+    pub fn transform(&self) -> Transform {
+        Transform::new(
+            self.position,
+            Quaternion::from_euler(self.rotation),
+            self.scale,
+        )
+    }
+    /// Rotates the mesh by a further rotation, on top of the one it already has. Unlike adding to ```rotation```, this always turns the mesh around the given axes in world space, no matter how it is already rotated.
+    /// EX:
+    /// ```
+    /// # use ascii_renderer::prelude::*;
+    /// let mut my_mesh = ascii_renderer::create_cube();
+    /// my_mesh.rotate_by(Quaternion::from_axis_angle(vec3!(0.0, 1.0, 0.0), 0.1));
+    /// ```
+    pub fn rotate_by(&mut self, rotation: Quaternion) {
+        self.rotation = (rotation * Quaternion::from_euler(self.rotation)).to_euler();
     }
     /// Gets the average position of all the vertices and centers the mesh to be centered around that point. Good for meshes you want to rotate.
     /// returns the global coords to where the mesh was previously centered. If the mesh's position is set to this, then the mesh will go back to it's previous position, only now it's center is appropriatly placed so rotation won't look broken.
     /// EX:
     /// ```
+    /// # let mut my_mesh = ascii_renderer::create_cube();
     /// let new_position = my_mesh.recenter();
     /// my_mesh.position = new_position;
     /// ```
@@ -213,10 +797,16 @@ impl std::default::Default for Mesh {
         Self {
             vertices: HashMap::new(),
             edges: vec![],
+            faces: vec![],
+            normals: HashMap::new(),
+            uvs: HashMap::new(),
             rotation: vec3!(0.0, 0.0, 0.0),
             position: vec3!(0.0, 0.0, 0.0),
             scale: vec3!(1.0, 1.0, 1.0),
-            char: '+',
+            stroke: Stroke::Char('+'),
+            color: None,
+            object: None,
+            groups: vec![],
         }
     }
 }
@@ -234,37 +824,64 @@ impl Vector3 {
         Self { x, y, z }
     }
     pub fn rotate(self, rotation_vec: Vector3) -> Self {
-        //Rotate around x
-        let mut ret = {
-            let z_y = vec2!(self.z, self.y).rotate(rotation_vec.x);
-            vec3!(self.x, z_y.y, z_y.x)
-        };
-
-        //Rotate around y
-        ret = {
-            let x_z = vec2!(ret.x, ret.z).rotate(rotation_vec.y);
-            vec3!(x_z.x, ret.y, x_z.y)
-        };
-
-        //Rotate around z
-        ret = {
-            let x_y = vec2!(ret.x, ret.y).rotate(rotation_vec.z);
-            vec3!(x_y.x, x_y.y, ret.z)
-        };
-
-        ret
+        //! Rotates around the x axis, then the y axis, then the z axis, by the angles in rotation_vec. To rotate lots of points by the same amount, build a ```Matrix3::rotation()``` once and multiply by it instead.
+        Matrix3::rotation(rotation_vec) * self
     }
     pub fn len(self) -> f32 {
         (self.x * self.x + self.y * self.y + self.z * self.z).sqrt()
     }
+    pub fn dot(self, other: Self) -> f32 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+    pub fn cross(self, other: Self) -> Self {
+        vec3!(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
     pub fn normalize(self) -> Self {
         let len = self.len();
         if len == 1.0 || len == 0.0 {
-            return self;
+            self
         } else {
             vec3!(self.x / len, self.y / len, self.z / len)
         }
     }
+    pub fn lerp(self, other: Self, t: f32) -> Self {
+        //! Linearly interpolates towards other, where t = 0.0 is self and t = 1.0 is other.
+        self + (other - self) * t
+    }
+    pub fn distance(self, other: Self) -> f32 {
+        (other - self).len()
+    }
+    pub fn angle_between(self, other: Self) -> f32 {
+        //! The angle between the two vectors in radians, from 0.0 to pi. Zero length vectors give 0.0.
+        let lens = self.len() * other.len();
+        if lens == 0.0 {
+            return 0.0;
+        }
+        (self.dot(other) / lens).clamp(-1.0, 1.0).acos()
+    }
+    pub fn reflect(self, normal: Self) -> Self {
+        //! Bounces the vector off a surface with the given normal, which doesn't need to be normalized.
+        let normal = normal.normalize();
+        self - normal * (2.0 * self.dot(normal))
+    }
+    pub fn project_onto(self, other: Self) -> Self {
+        //! The part of the vector that points along other. Projecting onto a zero length vector gives a zero vector.
+        let len_squared = other.dot(other);
+        if len_squared == 0.0 {
+            return vec3!(0.0, 0.0, 0.0);
+        }
+        other * (self.dot(other) / len_squared)
+    }
+    pub fn approx_eq(self, other: Self, epsilon: f32) -> bool {
+        //! Whether every component is within epsilon of other's.
+        (self.x - other.x).abs() <= epsilon
+            && (self.y - other.y).abs() <= epsilon
+            && (self.z - other.z).abs() <= epsilon
+    }
 }
 
 impl std::ops::Add for Vector3 {
@@ -289,9 +906,9 @@ impl std::ops::Sub for Vector3 {
     }
 }
 
-impl std::convert::Into<(f32, f32, f32)> for Vector3 {
-    fn into(self) -> (f32, f32, f32) {
-        (self.x, self.y, self.z)
+impl std::convert::From<Vector3> for (f32, f32, f32) {
+    fn from(vector: Vector3) -> Self {
+        (vector.x, vector.y, vector.z)
     }
 }
 
@@ -333,6 +950,67 @@ impl std::ops::DivAssign<f32> for Vector3 {
     }
 }
 
+impl std::ops::Mul for Vector3 {
+    type Output = Vector3;
+    fn mul(self, rhs: Self) -> Self::Output {
+        //! Multiplies each component by the matching component of rhs.
+        vec3!(self.x * rhs.x, self.y * rhs.y, self.z * rhs.z)
+    }
+}
+
+impl std::ops::MulAssign for Vector3 {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl std::ops::Index<usize> for Vector3 {
+    type Output = f32;
+    fn index(&self, index: usize) -> &Self::Output {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            _ => panic!("index {index} is out of range for a Vector3"),
+        }
+    }
+}
+
+impl std::ops::IndexMut<usize> for Vector3 {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            2 => &mut self.z,
+            _ => panic!("index {index} is out of range for a Vector3"),
+        }
+    }
+}
+
+impl std::convert::From<[f32; 3]> for Vector3 {
+    fn from(array: [f32; 3]) -> Self {
+        vec3!(array[0], array[1], array[2])
+    }
+}
+
+impl std::convert::From<Vector3> for [f32; 3] {
+    fn from(vector: Vector3) -> Self {
+        [vector.x, vector.y, vector.z]
+    }
+}
+
+impl std::iter::Sum for Vector3 {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(vec3!(0.0, 0.0, 0.0), |accum, vector| accum + vector)
+    }
+}
+
+impl<'a> std::iter::Sum<&'a Vector3> for Vector3 {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
 impl std::ops::Neg for Vector3 {
     type Output = Self;
     fn neg(self) -> Self::Output {
@@ -399,11 +1077,50 @@ impl Vector2 {
     pub fn normalize(self) -> Self {
         let len = self.len();
         if len == 1.0 || len == 0.0 {
-            return self;
+            self
         } else {
             vec2!(self.x / len, self.y / len)
         }
     }
+    pub fn dot(self, other: Self) -> f32 {
+        self.x * other.x + self.y * other.y
+    }
+    pub fn cross(self, other: Self) -> f32 {
+        //! The z of the cross product of the two vectors as if they were 3d, which is positive when other is anticlockwise from self (with +y up).
+        self.x * other.y - self.y * other.x
+    }
+    pub fn lerp(self, other: Self, t: f32) -> Self {
+        //! Linearly interpolates towards other, where t = 0.0 is self and t = 1.0 is other.
+        self + (other - self) * t
+    }
+    pub fn distance(self, other: Self) -> f32 {
+        (other - self).len()
+    }
+    pub fn angle_between(self, other: Self) -> f32 {
+        //! The angle between the two vectors in radians, from 0.0 to pi. Zero length vectors give 0.0.
+        let lens = self.len() * other.len();
+        if lens == 0.0 {
+            return 0.0;
+        }
+        (self.dot(other) / lens).clamp(-1.0, 1.0).acos()
+    }
+    pub fn reflect(self, normal: Self) -> Self {
+        //! Bounces the vector off a line with the given normal, which doesn't need to be normalized.
+        let normal = normal.normalize();
+        self - normal * (2.0 * self.dot(normal))
+    }
+    pub fn project_onto(self, other: Self) -> Self {
+        //! The part of the vector that points along other. Projecting onto a zero length vector gives a zero vector.
+        let len_squared = other.dot(other);
+        if len_squared == 0.0 {
+            return vec2!(0.0, 0.0);
+        }
+        other * (self.dot(other) / len_squared)
+    }
+    pub fn approx_eq(self, other: Self, epsilon: f32) -> bool {
+        //! Whether every component is within epsilon of other's.
+        (self.x - other.x).abs() <= epsilon && (self.y - other.y).abs() <= epsilon
+    }
 }
 
 impl std::ops::Add for Vector2 {
@@ -426,15 +1143,15 @@ impl std::ops::Sub for Vector2 {
     }
 }
 
-impl std::convert::Into<(f32, f32)> for Vector2 {
-    fn into(self) -> (f32, f32) {
-        (self.x, self.y)
+impl std::convert::From<Vector2> for (f32, f32) {
+    fn from(vector: Vector2) -> Self {
+        (vector.x, vector.y)
     }
 }
 
 impl std::ops::AddAssign for Vector2 {
     fn add_assign(&mut self, rhs: Self) {
-        *self = *self - rhs;
+        *self = *self + rhs;
     }
 }
 
@@ -470,6 +1187,65 @@ impl std::ops::DivAssign<f32> for Vector2 {
     }
 }
 
+impl std::ops::Mul for Vector2 {
+    type Output = Vector2;
+    fn mul(self, rhs: Self) -> Self::Output {
+        //! Multiplies each component by the matching component of rhs.
+        vec2!(self.x * rhs.x, self.y * rhs.y)
+    }
+}
+
+impl std::ops::MulAssign for Vector2 {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl std::ops::Index<usize> for Vector2 {
+    type Output = f32;
+    fn index(&self, index: usize) -> &Self::Output {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            _ => panic!("index {index} is out of range for a Vector2"),
+        }
+    }
+}
+
+impl std::ops::IndexMut<usize> for Vector2 {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            _ => panic!("index {index} is out of range for a Vector2"),
+        }
+    }
+}
+
+impl std::convert::From<[f32; 2]> for Vector2 {
+    fn from(array: [f32; 2]) -> Self {
+        vec2!(array[0], array[1])
+    }
+}
+
+impl std::convert::From<Vector2> for [f32; 2] {
+    fn from(vector: Vector2) -> Self {
+        [vector.x, vector.y]
+    }
+}
+
+impl std::iter::Sum for Vector2 {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(vec2!(0.0, 0.0), |accum, vector| accum + vector)
+    }
+}
+
+impl<'a> std::iter::Sum<&'a Vector2> for Vector2 {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
 impl std::ops::Neg for Vector2 {
     type Output = Self;
     fn neg(self) -> Self::Output {
@@ -489,3 +1265,658 @@ impl std::ops::SubAssign<Vector3> for Vector2 {
         *self = *self - rhs;
     }
 }
+
+/// A 3x3 matrix, stored as rows. Multiplying it by a Vector3 treats the vector as a column.
+/// ```Matrix3::rotation()``` rotates the same way as ```Vector3::rotate()```, but all three rotations are folded into one matrix, which is much cheaper to apply to lots of points.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Matrix3 {
+    pub rows: [[f32; 3]; 3],
+}
+
+impl Matrix3 {
+    pub const IDENTITY: Matrix3 = Matrix3 {
+        rows: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+    };
+    pub fn new(rows: [[f32; 3]; 3]) -> Self {
+        Self { rows }
+    }
+    pub fn rotation(rotation: Vector3) -> Self {
+        //! The matrix that rotates around x, then y, then z by the given angles, the same as ```Vector3::rotate()```.
+        let (sin_x, cos_x) = rotation.x.sin_cos();
+        let (sin_y, cos_y) = rotation.y.sin_cos();
+        let (sin_z, cos_z) = rotation.z.sin_cos();
+        let around_x = Matrix3::new([[1.0, 0.0, 0.0], [0.0, cos_x, sin_x], [0.0, -sin_x, cos_x]]);
+        let around_y = Matrix3::new([[cos_y, 0.0, -sin_y], [0.0, 1.0, 0.0], [sin_y, 0.0, cos_y]]);
+        let around_z = Matrix3::new([[cos_z, -sin_z, 0.0], [sin_z, cos_z, 0.0], [0.0, 0.0, 1.0]]);
+        around_z * around_y * around_x
+    }
+    pub fn scale(scale: Vector3) -> Self {
+        Matrix3::new([
+            [scale.x, 0.0, 0.0],
+            [0.0, scale.y, 0.0],
+            [0.0, 0.0, scale.z],
+        ])
+    }
+    pub fn transpose(self) -> Self {
+        //! Swaps rows and columns. For a rotation matrix, this is the rotation that undoes it.
+        let mut rows = [[0.0; 3]; 3];
+        for (i, row) in rows.iter_mut().enumerate() {
+            for (j, item) in row.iter_mut().enumerate() {
+                *item = self.rows[j][i];
+            }
+        }
+        Matrix3::new(rows)
+    }
+}
+
+impl std::ops::Mul for Matrix3 {
+    type Output = Matrix3;
+    fn mul(self, rhs: Self) -> Self::Output {
+        let mut rows = [[0.0; 3]; 3];
+        for (i, row) in rows.iter_mut().enumerate() {
+            for (j, item) in row.iter_mut().enumerate() {
+                *item = (0..3).map(|k| self.rows[i][k] * rhs.rows[k][j]).sum();
+            }
+        }
+        Matrix3::new(rows)
+    }
+}
+
+impl std::ops::Mul<Vector3> for Matrix3 {
+    type Output = Vector3;
+    fn mul(self, rhs: Vector3) -> Self::Output {
+        let [a, b, c] = self.rows;
+        vec3!(
+            a[0] * rhs.x + a[1] * rhs.y + a[2] * rhs.z,
+            b[0] * rhs.x + b[1] * rhs.y + b[2] * rhs.z,
+            c[0] * rhs.x + c[1] * rhs.y + c[2] * rhs.z,
+        )
+    }
+}
+
+impl std::convert::From<Quaternion> for Matrix3 {
+    fn from(q: Quaternion) -> Self {
+        //! The rotation matrix of a unit quaternion.
+        let Quaternion { w, x, y, z } = q;
+        Matrix3::new([
+            [
+                1.0 - 2.0 * (y * y + z * z),
+                2.0 * (x * y - w * z),
+                2.0 * (x * z + w * y),
+            ],
+            [
+                2.0 * (x * y + w * z),
+                1.0 - 2.0 * (x * x + z * z),
+                2.0 * (y * z - w * x),
+            ],
+            [
+                2.0 * (x * z - w * y),
+                2.0 * (y * z + w * x),
+                1.0 - 2.0 * (x * x + y * y),
+            ],
+        ])
+    }
+}
+
+/// A 4x4 matrix, stored as rows, for transformations that a Matrix3 can't do on its own like translation and perspective projection.
+/// Points are treated as columns with a w of 1, so a chain of matrices applies from right to left, e.g. ```translation * rotation * scale``` scales first.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Matrix4 {
+    pub rows: [[f32; 4]; 4],
+}
+
+impl Matrix4 {
+    pub const IDENTITY: Matrix4 = Matrix4 {
+        rows: [
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ],
+    };
+    pub fn new(rows: [[f32; 4]; 4]) -> Self {
+        Self { rows }
+    }
+    pub fn translation(offset: Vector3) -> Self {
+        let mut matrix = Matrix4::IDENTITY;
+        matrix.rows[0][3] = offset.x;
+        matrix.rows[1][3] = offset.y;
+        matrix.rows[2][3] = offset.z;
+        matrix
+    }
+    pub fn rotation(rotation: Vector3) -> Self {
+        //! See ```Matrix3::rotation()```.
+        Matrix3::rotation(rotation).into()
+    }
+    pub fn scale(scale: Vector3) -> Self {
+        Matrix3::scale(scale).into()
+    }
+    pub fn perspective(fov: Vector2, near: f32) -> Self {
+        //! A perspective projection for a camera at the origin looking down +z, with no far plane. Points in view are mapped to x and y between -1.0 and 1.0 (with +y downwards, the same as the screen), and z from -1.0 at the near plane towards 1.0 as they get further away. Each fov is the full angle across the view, and must be less than pi.
+        let scale_x = 1.0 / (fov.x / 2.0).tan();
+        let scale_y = 1.0 / (fov.y / 2.0).tan();
+        Matrix4::new([
+            [scale_x, 0.0, 0.0, 0.0],
+            [0.0, scale_y, 0.0, 0.0],
+            [0.0, 0.0, 1.0, -2.0 * near],
+            [0.0, 0.0, 1.0, 0.0],
+        ])
+    }
+    pub fn orthographic(size: Vector2) -> Self {
+        //! An orthographic projection for a camera at the origin looking down +z, which maps a view of the given width and height to x and y between -1.0 and 1.0 (with +y downwards, the same as the screen). z is left as it is.
+        Matrix4::new([
+            [2.0 / size.x, 0.0, 0.0, 0.0],
+            [0.0, 2.0 / size.y, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+    pub fn transform_point(&self, point: Vector3) -> Vector3 {
+        //! Transforms a point, including any translation. If the matrix is a projection, the result is divided through by w.
+        let [a, b, c, d] = self.rows;
+        let transformed = vec3!(
+            a[0] * point.x + a[1] * point.y + a[2] * point.z + a[3],
+            b[0] * point.x + b[1] * point.y + b[2] * point.z + b[3],
+            c[0] * point.x + c[1] * point.y + c[2] * point.z + c[3],
+        );
+        let w = d[0] * point.x + d[1] * point.y + d[2] * point.z + d[3];
+        if w == 1.0 {
+            transformed
+        } else {
+            transformed / w
+        }
+    }
+    pub fn transform_vector(&self, vector: Vector3) -> Vector3 {
+        //! Transforms a direction, which ignores any translation.
+        let [a, b, c, _] = self.rows;
+        vec3!(
+            a[0] * vector.x + a[1] * vector.y + a[2] * vector.z,
+            b[0] * vector.x + b[1] * vector.y + b[2] * vector.z,
+            c[0] * vector.x + c[1] * vector.y + c[2] * vector.z,
+        )
+    }
+}
+
+impl std::ops::Mul for Matrix4 {
+    type Output = Matrix4;
+    fn mul(self, rhs: Self) -> Self::Output {
+        let mut rows = [[0.0; 4]; 4];
+        for (i, row) in rows.iter_mut().enumerate() {
+            for (j, item) in row.iter_mut().enumerate() {
+                *item = (0..4).map(|k| self.rows[i][k] * rhs.rows[k][j]).sum();
+            }
+        }
+        Matrix4::new(rows)
+    }
+}
+
+impl std::convert::From<Matrix3> for Matrix4 {
+    fn from(matrix: Matrix3) -> Self {
+        let mut rows = Matrix4::IDENTITY.rows;
+        for (row, matrix_row) in rows.iter_mut().zip(matrix.rows.iter()) {
+            row[..3].copy_from_slice(matrix_row);
+        }
+        Matrix4::new(rows)
+    }
+}
+
+/// A rotation stored as a unit quaternion. Unlike a rotation vector of three angles, quaternions can be combined (with ```*```, where ```a * b``` rotates by b and then by a) without ever getting stuck in gimbal lock.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quaternion {
+    pub w: f32,
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+impl Quaternion {
+    pub const IDENTITY: Quaternion = Quaternion {
+        w: 1.0,
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+    };
+    pub fn new(w: f32, x: f32, y: f32, z: f32) -> Self {
+        Self { w, x, y, z }
+    }
+    pub fn from_axis_angle(axis: Vector3, angle: f32) -> Self {
+        //! A rotation by angle radians around axis, anticlockwise when looking back down the axis towards the origin.
+        let axis = axis.normalize();
+        let (sin, cos) = (angle / 2.0).sin_cos();
+        Quaternion::new(cos, axis.x * sin, axis.y * sin, axis.z * sin)
+    }
+    pub fn from_euler(rotation: Vector3) -> Self {
+        //! The same rotation as ```Vector3::rotate()``` with the given rotation vector.
+        Quaternion::from_axis_angle(vec3!(0.0, 0.0, 1.0), rotation.z)
+            * Quaternion::from_axis_angle(vec3!(0.0, 1.0, 0.0), -rotation.y)
+            * Quaternion::from_axis_angle(vec3!(1.0, 0.0, 0.0), -rotation.x)
+    }
+    pub fn to_euler(self) -> Vector3 {
+        //! Gets a rotation vector (as used by ```Vector3::rotate()``` and ```Mesh::rotation```) for the same rotation.
+        let rows = Matrix3::from(self.normalize()).rows;
+        let y = rows[2][0].clamp(-1.0, 1.0).asin();
+        if rows[2][0].abs() < 0.9999 {
+            vec3!(
+                (-rows[2][1]).atan2(rows[2][2]),
+                y,
+                rows[1][0].atan2(rows[0][0])
+            )
+        } else {
+            // Gimbal lock, where x and z rotate around the same axis, so all of it goes into z
+            vec3!(0.0, y, (-rows[0][1]).atan2(rows[1][1]))
+        }
+    }
+    pub fn conjugate(self) -> Self {
+        //! For a unit quaternion, the rotation that undoes this one.
+        Quaternion::new(self.w, -self.x, -self.y, -self.z)
+    }
+    pub fn len(self) -> f32 {
+        (self.w * self.w + self.x * self.x + self.y * self.y + self.z * self.z).sqrt()
+    }
+    pub fn normalize(self) -> Self {
+        let len = self.len();
+        if len == 1.0 || len == 0.0 {
+            self
+        } else {
+            Quaternion::new(self.w / len, self.x / len, self.y / len, self.z / len)
+        }
+    }
+    pub fn rotate_vector(self, vector: Vector3) -> Vector3 {
+        Matrix3::from(self) * vector
+    }
+}
+
+impl std::ops::Mul for Quaternion {
+    type Output = Quaternion;
+    fn mul(self, rhs: Self) -> Self::Output {
+        Quaternion::new(
+            self.w * rhs.w - self.x * rhs.x - self.y * rhs.y - self.z * rhs.z,
+            self.w * rhs.x + self.x * rhs.w + self.y * rhs.z - self.z * rhs.y,
+            self.w * rhs.y - self.x * rhs.z + self.y * rhs.w + self.z * rhs.x,
+            self.w * rhs.z + self.x * rhs.y - self.y * rhs.x + self.z * rhs.w,
+        )
+    }
+}
+
+impl std::default::Default for Quaternion {
+    fn default() -> Self {
+        Quaternion::IDENTITY
+    }
+}
+
+/// A position, rotation and scale, which together place something in the world. The matrix scales first, then rotates, then moves to the position.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+    pub position: Vector3,
+    pub rotation: Quaternion,
+    pub scale: Vector3,
+}
+
+impl Transform {
+    pub fn new(position: Vector3, rotation: Quaternion, scale: Vector3) -> Self {
+        Self {
+            position,
+            rotation,
+            scale,
+        }
+    }
+    pub fn matrix(&self) -> Matrix4 {
+        //! Composes the scale, rotation and translation into one matrix.
+        let mut matrix: Matrix4 =
+            (Matrix3::from(self.rotation) * Matrix3::scale(self.scale)).into();
+        matrix.rows[0][3] = self.position.x;
+        matrix.rows[1][3] = self.position.y;
+        matrix.rows[2][3] = self.position.z;
+        matrix
+    }
+    pub fn transform_point(&self, point: Vector3) -> Vector3 {
+        self.rotation.rotate_vector(vec3!(
+            point.x * self.scale.x,
+            point.y * self.scale.y,
+            point.z * self.scale.z
+        )) + self.position
+    }
+}
+
+impl std::default::Default for Transform {
+    fn default() -> Self {
+        Transform::new(
+            vec3!(0.0, 0.0, 0.0),
+            Quaternion::IDENTITY,
+            vec3!(1.0, 1.0, 1.0),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn camera() -> Camera {
+        Camera::new(vec3!(0.0, 0.0, 0.0), vec3!(0.0, 0.0, 0.0), vec2!(1.0, 1.0))
+    }
+
+    fn assert_close(a: Vector3, b: Vector3) {
+        assert!((a - b).len() < 1e-4, "{a:?} != {b:?}");
+    }
+
+    #[test]
+    fn default_shading() {
+        let shading = Shading::default();
+        assert_eq!(shading.char_for(vec3!(0.0, -1.0, -1.0)), '@');
+        // Faces turned away from the light get the darkest step, which is still visible
+        assert_eq!(shading.char_for(vec3!(0.0, 1.0, 1.0)), '.');
+        assert_eq!(shading.char_for(vec3!(1.0, 0.0, 0.0)), '.');
+    }
+
+    #[test]
+    fn renderer_is_thread_safe() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Renderer>();
+    }
+
+    #[test]
+    fn auto_aspect() {
+        let mut camera = camera();
+        assert_eq!(camera.fitted((40, 20)).fov, camera.fov);
+        camera.auto_aspect = true;
+        let fov = camera.fitted((40, 20)).fov;
+        assert!(((fov.x / 2.0).tan() - 2.0 * 0.5f32.tan()).abs() < 1e-5);
+        assert_eq!(camera.fitted((40, 0)).fov, camera.fov);
+        camera.cell_aspect = 0.5;
+        assert!((camera.fitted((40, 20)).fov.x - 1.0).abs() < 1e-5);
+
+        // A point just outside the fov across is on screen once the fov is widened to fit the buffer
+        camera.cell_aspect = 1.0;
+        let point = vec3!(0.6f32.tan(), 0.0, 1.0);
+        assert!(camera.is_pnt_on_buffer(point, (40, 20)));
+        assert!(!camera.is_pnt_on_buffer(point, (20, 20)));
+        assert!(!camera.is_pnt_on_buffer(vec3!(0.0, 0.6f32.tan(), 1.0), (40, 20)));
+        // Without a buffer to fit to, the camera's own fov is used
+        assert!(!camera.is_pnt_on_screen(point));
+        assert!(camera.is_pnt_on_screen(vec3!(0.4f32.tan(), 0.0, 1.0)));
+        camera.auto_aspect = false;
+        assert!(!camera.is_pnt_on_buffer(point, (40, 20)));
+    }
+
+    #[test]
+    fn orthographic_projection() {
+        let mut camera = Camera::new_orthographic(
+            vec3!(0.0, 0.0, -10.0),
+            vec3!(0.0, 0.0, 0.0),
+            vec2!(4.0, 2.0),
+        );
+        assert!(camera.is_pnt_on_screen(vec3!(1.9, -0.9, 100.0)));
+        assert!(!camera.is_pnt_on_screen(vec3!(2.1, 0.0, 0.0)));
+        assert!(!camera.is_pnt_on_screen(vec3!(0.0, 0.0, -20.0)));
+        // Distance doesn't change where a point ends up
+        assert_eq!(camera.map_point_uv(vec3!(1.0, 0.5, 0.0)), vec2!(0.75, 0.75));
+        assert_eq!(
+            camera.map_point_uv(vec3!(1.0, 0.5, 50.0)),
+            vec2!(0.75, 0.75)
+        );
+        let projected = camera
+            .projection_matrix((10, 10))
+            .transform_point(vec3!(1.0, 0.5, 5.0));
+        assert_eq!(projected, vec3!(0.5, 0.5, 5.0));
+
+        camera.auto_aspect = true;
+        assert_eq!(
+            camera.fitted((30, 10)).projection,
+            Projection::Orthographic {
+                size: vec2!(6.0, 2.0)
+            }
+        );
+    }
+
+    #[test]
+    fn labels() {
+        use crate::char_buffer::CharBuffer;
+        let mut renderer = Renderer::new(vec![], camera());
+        renderer.labels = vec![
+            Label::new(vec3!(0.0, 0.0, 5.0), "mid"),
+            Label::new(vec3!(-2.4, 0.0, 5.0), "edge"),
+            Label::new(vec3!(0.0, 0.0, -5.0), "behind"),
+        ];
+        let mut buf = CharBuffer::new(10, 3);
+        renderer.draw(&mut buf);
+        assert_eq!(buf.data[1].iter().collect::<String>(), "ge  mid   ");
+        assert!(buf.data[0]
+            .iter()
+            .chain(buf.data[2].iter())
+            .all(|&char| char == ' '));
+    }
+
+    #[test]
+    fn look_at() {
+        let targets = [
+            vec3!(3.0, -2.0, 7.0),
+            vec3!(-4.0, 1.0, -0.5),
+            vec3!(5.0, 0.0, 0.0), // Straight along x
+            vec3!(-5.0, 0.0, 0.0),
+            vec3!(0.0, 0.0, -5.0),
+        ];
+        for up in [vec3!(0.0, -1.0, 0.0), vec3!(0.0, 1.0, 0.0)] {
+            for target in targets {
+                let mut camera =
+                    Camera::new(vec3!(0.0, 0.0, 0.0), vec3!(0.0, 0.0, 0.0), vec2!(1.0, 1.0));
+                camera.look_at(target, up);
+                // The target is dead ahead, and up is up the screen, which is -y in view space
+                assert_close(camera.to_view_space(target), vec3!(0.0, 0.0, target.len()));
+                let above = camera.to_view_space(up);
+                assert!(
+                    above.y < -0.5 && above.x.abs() < 1e-4,
+                    "{target:?} {above:?}"
+                );
+            }
+        }
+
+        // Looking straight along up still faces the target
+        let mut camera = Camera::new(vec3!(0.0, 0.0, 0.0), vec3!(0.0, 0.0, 0.0), vec2!(1.0, 1.0));
+        camera.look_at(vec3!(0.0, -3.0, 0.0), vec3!(0.0, -1.0, 0.0));
+        assert_close(
+            camera.to_view_space(vec3!(0.0, -3.0, 0.0)),
+            vec3!(0.0, 0.0, 3.0),
+        );
+        let rotation = camera.rotation;
+        camera.look_at(camera.position, vec3!(0.0, -1.0, 0.0));
+        assert_eq!(camera.rotation, rotation);
+    }
+
+    #[test]
+    fn vector3_math() {
+        let a = vec3!(1.0, 2.0, 3.0);
+        let b = vec3!(-2.0, 0.5, 1.0);
+        assert_eq!(a.dot(b), 2.0);
+        assert_eq!(a.cross(b), vec3!(0.5, -7.0, 4.5));
+        assert_eq!(a.lerp(b, 0.5), vec3!(-0.5, 1.25, 2.0));
+        assert_eq!(vec3!(1.0, 1.0, 1.0).distance(vec3!(1.0, 4.0, 5.0)), 5.0);
+        assert!(
+            (vec3!(1.0, 0.0, 0.0).angle_between(vec3!(0.0, 0.0, 2.0))
+                - std::f32::consts::FRAC_PI_2)
+                .abs()
+                < 1e-6
+        );
+        assert_eq!(a.angle_between(vec3!(0.0, 0.0, 0.0)), 0.0);
+        assert_eq!(
+            vec3!(1.0, -1.0, 0.0).reflect(vec3!(0.0, 2.0, 0.0)),
+            vec3!(1.0, 1.0, 0.0)
+        );
+        assert_eq!(a.project_onto(vec3!(0.0, 0.0, 2.0)), vec3!(0.0, 0.0, 3.0));
+        assert_eq!(a * b, vec3!(-2.0, 1.0, 3.0));
+        assert!(a.approx_eq(a + vec3!(0.0, 1e-4, 0.0), 1e-3));
+        assert!(!a.approx_eq(b, 1e-3));
+
+        let mut c = a;
+        c[2] = 5.0;
+        assert_eq!((c[0], c[1], c[2]), (1.0, 2.0, 5.0));
+        assert_eq!(Vector3::from([1.0, 2.0, 3.0]), a);
+        assert_eq!(<[f32; 3]>::from(a), [1.0, 2.0, 3.0]);
+        assert_eq!([a, b].iter().sum::<Vector3>(), vec3!(-1.0, 2.5, 4.0));
+    }
+
+    #[test]
+    fn vector2_math() {
+        let a = vec2!(3.0, 4.0);
+        let b = vec2!(-1.0, 2.0);
+        assert_eq!(a.dot(b), 5.0);
+        assert_eq!(a.cross(b), 10.0);
+        assert_eq!(a.lerp(b, 0.25), vec2!(2.0, 3.5));
+        assert_eq!(a.distance(vec2!(0.0, 0.0)), 5.0);
+        assert_eq!(vec2!(1.0, -1.0).reflect(vec2!(0.0, 1.0)), vec2!(1.0, 1.0));
+        assert_eq!(a.project_onto(vec2!(2.0, 0.0)), vec2!(3.0, 0.0));
+        assert_eq!(a * b, vec2!(-3.0, 8.0));
+        assert!(a.approx_eq(vec2!(3.0005, 4.0), 1e-3));
+
+        let mut c = a;
+        c += b; //AddAssign used to subtract
+        assert_eq!(c, vec2!(2.0, 6.0));
+        c[0] = 1.0;
+        assert_eq!(c[0], 1.0);
+        assert_eq!(Vector2::from([3.0, 4.0]), a);
+        assert_eq!(<[f32; 2]>::from(a), [3.0, 4.0]);
+        assert_eq!(vec![a, b].into_iter().sum::<Vector2>(), vec2!(2.0, 6.0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn vector_index_bounds_check() {
+        let _ = vec3!(1.0, 2.0, 3.0)[3];
+    }
+
+    #[test]
+    fn rotation_matches_polar_rotation() {
+        // The original three polar rotations that Matrix3::rotation replaced
+        fn polar_rotate(point: Vector3, rotation: Vector3) -> Vector3 {
+            let z_y = vec2!(point.z, point.y).rotate(rotation.x);
+            let point = vec3!(point.x, z_y.y, z_y.x);
+            let x_z = vec2!(point.x, point.z).rotate(rotation.y);
+            let point = vec3!(x_z.x, point.y, x_z.y);
+            let x_y = vec2!(point.x, point.y).rotate(rotation.z);
+            vec3!(x_y.x, x_y.y, point.z)
+        }
+        let point = vec3!(1.0, -2.0, 3.0);
+        for rotation in [
+            vec3!(0.3, 0.0, 0.0),
+            vec3!(0.0, -1.2, 0.0),
+            vec3!(0.0, 0.0, 2.5),
+            vec3!(0.7, 1.9, -0.4),
+        ] {
+            assert_close(point.rotate(rotation), polar_rotate(point, rotation));
+            assert_close(
+                Quaternion::from_euler(rotation).rotate_vector(point),
+                polar_rotate(point, rotation),
+            );
+        }
+    }
+
+    #[test]
+    fn quaternion_euler_round_trip() {
+        let point = vec3!(1.0, -2.0, 3.0);
+        for rotation in [
+            vec3!(0.7, 1.2, -0.4),
+            vec3!(0.5, std::f32::consts::FRAC_PI_2, 0.3),
+        ] {
+            let euler = Quaternion::from_euler(rotation).to_euler();
+            assert_close(point.rotate(euler), point.rotate(rotation));
+        }
+
+        let turn = Quaternion::from_axis_angle(vec3!(0.0, 0.0, 1.0), std::f32::consts::FRAC_PI_2);
+        assert_close(
+            turn.rotate_vector(vec3!(1.0, 0.0, 0.0)),
+            vec3!(0.0, 1.0, 0.0),
+        );
+        assert_close((turn.conjugate() * turn).rotate_vector(point), point);
+    }
+
+    #[test]
+    fn transforms() {
+        let mut mesh = crate::create_cube();
+        mesh.position = vec3!(1.0, 2.0, 3.0);
+        mesh.rotation = vec3!(0.4, -0.2, 1.1);
+        mesh.scale = vec3!(2.0, 1.0, 0.5);
+        let transform = mesh.transform();
+        let point = vec3!(1.0, -1.0, 1.0);
+        let expected = vec3!(2.0, -1.0, 0.5).rotate(mesh.rotation) + mesh.position;
+        assert_close(mesh.model_matrix().transform_point(point), expected);
+        assert_close(transform.matrix().transform_point(point), expected);
+        assert_close(transform.transform_point(point), expected);
+
+        let camera = Camera::new(vec3!(1.0, 0.0, -5.0), vec3!(0.1, 0.2, 0.3), vec2!(1.0, 1.0));
+        assert_close(
+            camera.view_matrix().transform_point(point),
+            camera.to_view_space(point),
+        );
+    }
+
+    #[test]
+    fn perspective_projection() {
+        let projection = Matrix4::perspective(vec2!(1.0, 2.0), 0.1);
+        let center = projection.transform_point(vec3!(0.0, 0.0, 5.0));
+        assert!(center.x.abs() < 1e-5 && center.y.abs() < 1e-5);
+        let corner =
+            projection.transform_point(vec3!(0.5f32.tan() * 4.0, -1.0f32.tan() * 4.0, 4.0));
+        assert!((corner.x - 1.0).abs() < 1e-5 && (corner.y + 1.0).abs() < 1e-5);
+        assert!((projection.transform_point(vec3!(0.0, 0.0, 0.1)).z + 1.0).abs() < 1e-5);
+
+        let camera = camera();
+        let uv = camera.map_view_point_uv(vec3!(0.5f32.tan() * 2.0, 0.0, 2.0));
+        assert!((uv.x - 1.0).abs() < 1e-5 && (uv.y - 0.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn near_plane_edge_clipping() {
+        let camera = camera();
+        let inside = vec3!(0.0, 0.0, 2.0);
+        assert_eq!(
+            camera.clip_view_edge((inside, vec3!(0.0, 0.0, 1.0))),
+            Some((inside, vec3!(0.0, 0.0, 1.0)))
+        );
+        assert_eq!(
+            camera.clip_view_edge((vec3!(0.0, 0.0, -1.0), vec3!(1.0, 0.0, 0.05))),
+            None
+        );
+        let (start, end) = camera
+            .clip_view_edge((vec3!(0.0, 2.0, -1.9), vec3!(0.0, 0.0, 2.1)))
+            .unwrap();
+        assert!((start.z - 0.1).abs() < 1e-6);
+        assert!((start.y - 1.0).abs() < 1e-6);
+        assert_eq!(end, vec3!(0.0, 0.0, 2.1));
+    }
+
+    #[test]
+    fn near_plane_polygon_clipping() {
+        let camera = camera();
+        let triangle = [
+            vec3!(0.0, 0.0, 1.0),
+            vec3!(1.0, 0.0, 1.0),
+            vec3!(0.0, 1.0, 1.0),
+        ];
+        assert_eq!(camera.clip_view_polygon(&triangle), triangle.to_vec());
+
+        // One corner behind the camera turns the triangle into a quad
+        let clipped = camera.clip_view_polygon(&[
+            vec3!(0.0, 0.0, -1.0),
+            vec3!(1.0, 0.0, 1.0),
+            vec3!(0.0, 1.0, 1.0),
+        ]);
+        assert_eq!(clipped.len(), 4);
+        assert!(clipped.iter().all(|point| point.z >= camera.near - 1e-6));
+
+        // Two corners behind the camera leaves a smaller triangle
+        let clipped = camera.clip_view_polygon(&[
+            vec3!(0.0, 0.0, -1.0),
+            vec3!(1.0, 0.0, -1.0),
+            vec3!(0.0, 1.0, 1.0),
+        ]);
+        assert_eq!(clipped.len(), 3);
+
+        assert!(camera
+            .clip_view_polygon(&[
+                vec3!(0.0, 0.0, -1.0),
+                vec3!(1.0, 0.0, -1.0),
+                vec3!(0.0, 1.0, 0.0),
+            ])
+            .is_empty());
+    }
+}
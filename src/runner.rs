@@ -1,10 +1,12 @@
 use super::char_buffer::CharBuffer;
+use super::terminal::TerminalSession;
 use crossterm::{
     cursor::{Hide, MoveTo},
-    execute,
-    style::Print,
+    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    queue, terminal,
 };
-use std::time::Instant;
+use std::io::Write;
+use std::time::{Duration, Instant};
 
 ///The enum returned by the process fn of a logic class. If End is returned, the runner will cease, otherwise it will continue.
 pub enum ProcessReturn {
@@ -12,43 +14,151 @@ pub enum ProcessReturn {
     End,
 }
 
-///The trait used to define the behaviour of a runner.
-pub trait Logic {
-    ///This method runs every frame, is passed the the char buffer (which is maintained from frame to frame, so remember to clear it), delta (the time in secods since the last frame), and returns a ProcessReturn. After being running process every single frame, the runner will print the buffer to the screen. If ProcessReturn::Continue is returned the runner will then continue to the next frame, otherwise it will stop.
-    fn process(&mut self, screen_buf: &mut CharBuffer, delta: f32) -> ProcessReturn;
+///The trait used to define the behaviour of a runner. By default the runner hands the logic a CharBuffer, but implementing ```Logic<S>``` for another Screen (like ```Logic<PixelBuffer>```) makes the runner use that instead.
+pub trait Logic<S = CharBuffer> {
+    ///This method runs every frame, is passed the the screen buffer (which is maintained from frame to frame, so remember to clear it), delta (the time in secods since the last frame), and returns a ProcessReturn. After being running process every single frame, the runner will print the buffer to the screen. If ProcessReturn::Continue is returned the runner will then continue to the next frame, otherwise it will stop.
+    fn process(&mut self, screen_buf: &mut S, delta: f32) -> ProcessReturn;
+    ///Runs once for every input event (key presses and releases, mouse clicks, drags and scrolls, and terminal resizes) that arrived since the last frame, right before process. Only called if the runner's capture_input is true. Key releases are only reported by terminals that support them.
+    fn event(&mut self, _event: &Event) {}
 }
 
-///The struct that runs every thing. When ran, every single frame it will run the process method from it's logic (which will mutate the CharBuffer), print the char buffer to the screen, and if process returned continue it will wait for the next frame.
+///A buffer that a runner can own, hand to its logic every frame, and then print to the terminal.
+/// When drawing over the last frame, the runner keeps a copy of the frame it last showed to compare the next one against, which it updates every frame with ```clone_from()```. Implementing ```clone_from()``` to reuse the copy's memory (as the buffers in this crate do) saves allocating a whole screen every frame.
+pub trait Screen: Clone {
+    ///Creates a blank screen that takes up width x height cells of the terminal.
+    fn new(width: usize, height: usize) -> Self;
+    ///Changes the screen to take up width x height cells, keeping whatever fits unless clear is true.
+    fn resize(&mut self, width: usize, height: usize, clear: bool);
+    ///How many cells across and down fit in a terminal of the given size. By default every cell takes two columns (its char and the space after it) and one row, and the bottom row is left free for the newline after the last row.
+    fn size_for_terminal(&self, columns: u16, rows: u16) -> (usize, usize) {
+        (columns as usize / 2, rows.saturating_sub(1) as usize)
+    }
+    ///Writes the whole screen, row by row with a newline after each row, including any escape sequences it needs.
+    fn write_to(&self, out: &mut dyn Write) -> std::io::Result<()>;
+    ///Moves the cursor to the top left of the terminal and writes the whole screen over whatever is there.
+    fn redraw(&self, out: &mut dyn Write) -> std::io::Result<()> {
+        write!(out, "{}", MoveTo(0, 0))?;
+        self.write_to(out)
+    }
+    ///Writes only what changed since previous was drawn, assuming the terminal is still showing previous at its top left. Screens that can't tell what changed redraw everything, which is what this does by default.
+    fn write_changes(&self, previous: &Self, out: &mut dyn Write) -> std::io::Result<()> {
+        let _ = previous;
+        self.redraw(out)
+    }
+}
+
+impl Screen for CharBuffer {
+    fn new(width: usize, height: usize) -> Self {
+        CharBuffer::new(width, height)
+    }
+    fn resize(&mut self, width: usize, height: usize, clear: bool) {
+        CharBuffer::resize(self, width, height, clear);
+    }
+    fn size_for_terminal(&self, columns: u16, rows: u16) -> (usize, usize) {
+        //! Unpadded buffers fit a cell in every column.
+        let columns_per_cell = if self.padded { 2 } else { 1 };
+        (
+            columns as usize / columns_per_cell,
+            rows.saturating_sub(1) as usize,
+        )
+    }
+    fn write_to(&self, out: &mut dyn Write) -> std::io::Result<()> {
+        write!(out, "{self}")
+    }
+    fn write_changes(&self, previous: &Self, out: &mut dyn Write) -> std::io::Result<()> {
+        //! Writes each changed char in place, only moving the cursor when the next changed char isn't right after the last one written. If the size or padding changed since previous, the terminal is cleared and the whole buffer is written instead.
+        if self.dimensions != previous.dimensions || self.padded != previous.padded {
+            // The last frame may have covered parts of the terminal that this one doesn't
+            write!(out, "{}", terminal::Clear(terminal::ClearType::All))?;
+            return self.redraw(out);
+        }
+        let mut cursor = None;
+        for (y, (row, previous_row)) in self.data.iter().zip(previous.data.iter()).enumerate() {
+            for (x, (char, previous_char)) in row.iter().zip(previous_row.iter()).enumerate() {
+                if char == previous_char {
+                    continue;
+                }
+                if cursor != Some((x, y)) {
+                    let column = if self.padded { x * 2 } else { x };
+                    write!(out, "{}", MoveTo(column as u16, y as u16))?;
+                }
+                if self.padded {
+                    write!(out, "{char} ")?;
+                } else {
+                    write!(out, "{char}")?;
+                }
+                cursor = Some((x + 1, y));
+            }
+        }
+        Ok(())
+    }
+}
+
+///The struct that runs every thing. When ran, every single frame it will run the process method from it's logic (which will mutate the screen buffer, a CharBuffer unless the logic asks for another Screen), print the screen buffer to the terminal, and if process returned continue it will wait for the next frame.
 /// # Example
-/// ```
+/// ```no_run
+/// # use ascii_renderer::prelude::*;
 /// struct MyLogic;
 /// impl Logic for MyLogic {
 ///     fn process(&mut self, screen_buf: &mut CharBuffer, delta: f32) -> ProcessReturn {
-///         let fps_string = delta.to_string();
-///         let chars = fps_string.chars();
-///         screen_buf.set_char(1, 1, chars.next().unwrap()).unwrap();
-///         screen_buf.set_char(2, 1, chars.nest().unwrap()).unwrap();
+///         let fps_string = (1.0 / delta).round().to_string();
+///         let mut chars = fps_string.chars();
+///         screen_buf.set_char(1, 1, chars.next().unwrap_or(' ')).unwrap();
+///         screen_buf.set_char(2, 1, chars.next().unwrap_or(' ')).unwrap();
+///         ProcessReturn::Continue
 ///     }
 /// }
 /// let mut my_runner = Runner::new(3, 3, 25, MyLogic);   //Should print the real fps to the screen every frame.
 /// my_runner.run(true);
 /// ```
-pub struct Runner<L: Logic> {
-    pub buf: CharBuffer,
+pub struct Runner<L: Logic<S>, S: Screen = CharBuffer> {
+    pub buf: S,
     logic: L,
     pub fps_cap: usize,
     last_timpoint: Instant,
+    presented: Option<S>,
+    ///If true, run puts the terminal in raw mode and hands every keyboard and mouse event to the logic's event method.
+    pub capture_input: bool,
+    ///If true, the buffer is resized to fill the terminal at the start of every frame where the terminal's size has changed.
+    pub auto_size: bool,
+    fitted_size: Option<(usize, usize)>,
+    session: Option<TerminalSession>,
 }
 
-impl<L: Logic> Runner<L> {
+impl<S: Screen, L: Logic<S>> Runner<L, S> {
     pub fn new(width: usize, height: usize, fps_cap: usize, logic: L) -> Self {
         Self {
-            buf: CharBuffer::new(width, height),
+            buf: S::new(width, height),
             logic,
             fps_cap,
             last_timpoint: Instant::now(),
+            presented: None,
+            capture_input: false,
+            auto_size: false,
+            fitted_size: None,
+            session: None,
         }
     }
+    pub fn new_auto_sized(fps_cap: usize, logic: L) -> Self {
+        //! Creates a runner whose buffer always fills the terminal, see auto_size.
+        let mut runner = Self::new(0, 0, fps_cap, logic);
+        runner.auto_size = true;
+        runner.fit_to_terminal();
+        runner
+    }
+    pub fn fit_to_terminal(&mut self) {
+        //! Resizes the buffer to fill the terminal if it no longer fits it exactly, e.g. because the terminal was resized. The buffer is cleared when it is resized, and the next frame is redrawn in full.
+        let Ok((columns, rows)) = terminal::size() else {
+            return;
+        };
+        let size = self.buf.size_for_terminal(columns, rows);
+        if self.fitted_size == Some(size) {
+            return;
+        }
+        self.fitted_size = Some(size);
+        self.buf.resize(size.0, size.1, true);
+        self.force_redraw();
+    }
     pub fn step(&mut self, clear_screen: bool) -> ProcessReturn {
         //! Steps the runner one frame. If the time since the last frame (or the creation of the runner) is less than 1.0 / fps_cap, it will instead wait until enough time has passed and then return continue, so typically it is better to run this twice to ensure a frame will actually render.
         let time_elapsed = self.last_timpoint.elapsed().as_secs_f32();
@@ -60,26 +170,193 @@ impl<L: Logic> Runner<L> {
         }
         self.last_timpoint = Instant::now();
 
+        if self
+            .session
+            .as_ref()
+            .is_some_and(|session| session.interrupted())
+        {
+            return ProcessReturn::End;
+        }
+        if self.capture_input && self.poll_events() {
+            return ProcessReturn::End;
+        }
+        if self.auto_size {
+            self.fit_to_terminal();
+        }
         let ret = self.logic.process(&mut self.buf, time_elapsed);
-        if clear_screen {
-            execute!(
-                std::io::stdout(),
-                Hide,
-                MoveTo(0, 0),
-                Print(format!("{buf}", buf = self.buf)),
-            )
-            .unwrap();
+        if self.capture_input {
+            // Raw mode doesn't return to the start of the line on a newline
+            self.present(&mut CrlfWriter(std::io::stdout().lock()), clear_screen)
         } else {
-            println!("{buf}", buf = self.buf);
+            self.present(&mut std::io::stdout().lock(), clear_screen)
         }
+        .unwrap();
         ret
     }
+    fn poll_events(&mut self) -> bool {
+        //! Hands every waiting event to the logic without blocking. Returns true if Ctrl-C was pressed.
+        // Errors reading the terminal (e.g. when it has been closed) just leave the rest of the events for the next frame
+        while let Ok(true) = event::poll(Duration::ZERO) {
+            let Ok(event) = event::read() else {
+                break;
+            };
+            if let Event::Resize(..) = event {
+                self.force_redraw();
+            }
+            self.logic.event(&event);
+            if let Event::Key(key) = event {
+                if key.code == KeyCode::Char('c')
+                    && key.modifiers.contains(KeyModifiers::CONTROL)
+                    && key.kind == KeyEventKind::Press
+                {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+    pub fn present(&mut self, out: &mut impl Write, clear_screen: bool) -> std::io::Result<()> {
+        //! Writes the buffer to out, then flushes it once. If clear_screen is true, only what changed since the last frame presented is written over it, unless it is the first frame or a redraw was forced. Otherwise the whole frame is written below the last one.
+        if clear_screen {
+            match &mut self.presented {
+                Some(previous) => {
+                    self.buf.write_changes(previous, out)?;
+                    previous.clone_from(&self.buf);
+                }
+                None => {
+                    queue!(out, Hide)?;
+                    self.buf.redraw(out)?;
+                    self.presented = Some(self.buf.clone());
+                }
+            }
+        } else {
+            self.buf.write_to(out)?;
+            writeln!(out)?;
+        }
+        out.flush()
+    }
+    pub fn force_redraw(&mut self) {
+        //! Makes the next frame redraw the whole screen rather than just what changed, e.g. after something else has written to the terminal.
+        self.presented = None;
+    }
     pub fn run(&mut self, clear_screen: bool) {
-        //! Runs the runner. If clear_screen is true, it will attempt to clear the terminal every frame. Otherwise, it will just print out every frame normally. It requires &mut self as the char buffer and logic will likely mutate every frame.
+        //! Runs the runner. If clear_screen is true, it will draw every frame over the last one, only rewriting the cells that changed. Otherwise, it will just print out every frame normally. It requires &mut self as the char buffer and logic will likely mutate every frame.
+        //! While running, the terminal is set up by a TerminalSession, which switches to the alternate screen if clear_screen is true and enables raw mode if capture_input is true. Everything is put back once the runner ends, whether that's from process returning End, a panic or Ctrl-C.
+        //! Unless the program already has its own Ctrl-C handler, this installs TerminalSession::install_signal_handler(), so Ctrl-C ends the runner at the next frame, and a second Ctrl-C puts the terminal back and kills the program straight away.
+        // An error only means a handler is already there, which is either ours or one the user wants to keep
+        let _ = TerminalSession::install_signal_handler();
+        self.session = Some(TerminalSession::start(clear_screen, self.capture_input).unwrap());
         loop {
             if let ProcessReturn::End = self.step(clear_screen) {
                 break;
             }
         }
+        self.session = None;
+    }
+}
+
+/// Turns every newline written through it into a carriage return and a newline.
+struct CrlfWriter<W: Write>(W);
+
+impl<W: Write> Write for CrlfWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let mut lines = buf.split(|&byte| byte == b'\n');
+        if let Some(first) = lines.next() {
+            self.0.write_all(first)?;
+        }
+        for line in lines {
+            self.0.write_all(b"\r\n")?;
+            self.0.write_all(line)?;
+        }
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.0.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct NoLogic;
+    impl Logic for NoLogic {
+        fn process(&mut self, _screen_buf: &mut CharBuffer, _delta: f32) -> ProcessReturn {
+            ProcessReturn::End
+        }
+    }
+
+    fn present(runner: &mut Runner<NoLogic>) -> String {
+        let mut out = vec![];
+        runner.present(&mut out, true).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn only_changes_are_written() {
+        let mut runner = Runner::new(4, 2, 25, NoLogic);
+        runner.buf.set_char(0, 0, 'a').unwrap();
+        assert_eq!(
+            present(&mut runner),
+            format!("{}{}a       \n        \n", Hide, MoveTo(0, 0))
+        );
+
+        runner.buf.set_char(1, 0, 'b').unwrap();
+        runner.buf.set_char(2, 0, 'c').unwrap();
+        runner.buf.set_char(1, 1, 'd').unwrap();
+        assert_eq!(
+            present(&mut runner),
+            format!("{}b c {}d ", MoveTo(2, 0), MoveTo(2, 1))
+        );
+
+        assert_eq!(present(&mut runner), "");
+
+        runner.buf.set_char(0, 0, ' ').unwrap();
+        runner.buf.set_char(3, 0, 'e').unwrap();
+        assert_eq!(
+            present(&mut runner),
+            format!("{}  {}e ", MoveTo(0, 0), MoveTo(6, 0))
+        );
+    }
+
+    #[test]
+    fn raw_mode_newlines() {
+        let mut out = CrlfWriter(vec![]);
+        write!(out, "a \n\nb \n").unwrap();
+        assert_eq!(&out.0, b"a \r\n\r\nb \r\n");
+    }
+
+    #[test]
+    fn unpadded_changes() {
+        let mut runner = Runner::new(3, 1, 25, NoLogic);
+        runner.buf.padded = false;
+        present(&mut runner);
+        runner.buf.set_char(1, 0, 'a').unwrap();
+        runner.buf.set_char(2, 0, 'b').unwrap();
+        assert_eq!(present(&mut runner), format!("{}ab", MoveTo(1, 0)));
+
+        // Changing the padding moves every cell, so the whole frame is written again
+        runner.buf.padded = true;
+        assert_eq!(
+            present(&mut runner),
+            format!(
+                "{}{}  a b \n",
+                terminal::Clear(terminal::ClearType::All),
+                MoveTo(0, 0)
+            )
+        );
+        assert_eq!(present(&mut runner), "");
+    }
+
+    #[test]
+    fn forced_redraw() {
+        let mut runner = Runner::new(2, 1, 25, NoLogic);
+        present(&mut runner);
+        runner.buf.set_char(1, 0, 'x').unwrap();
+        runner.force_redraw();
+        assert_eq!(
+            present(&mut runner),
+            format!("{}{}  x \n", Hide, MoveTo(0, 0))
+        );
     }
 }
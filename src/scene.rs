@@ -0,0 +1,286 @@
+use super::rendering::{Matrix4, Mesh, Transform};
+
+///A handle to a node in a Scene. Handles stay valid until their node is removed, after which they never point at another node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId(usize);
+
+///Something placed in a Scene. Its transform is relative to its parent (or to the world, for nodes without a parent), so moving, turning or scaling a node carries all of its children along with it.
+/// A node's mesh is drawn with the mesh's own position, rotation and scale applied first, then the node's world transform. Hidden nodes aren't drawn, and neither are any of their children.
+#[derive(Debug, Clone)]
+pub struct Node {
+    pub name: String,
+    pub transform: Transform,
+    pub mesh: Option<Mesh>,
+    pub visible: bool,
+    parent: Option<NodeId>,
+    children: Vec<NodeId>,
+}
+
+impl Node {
+    pub fn new(name: &str) -> Self {
+        //! Creates an empty node, useful for grouping other nodes or as a pivot to turn them around.
+        Node {
+            name: name.to_owned(),
+            transform: Transform::default(),
+            mesh: None,
+            visible: true,
+            parent: None,
+            children: vec![],
+        }
+    }
+    pub fn with_mesh(name: &str, mesh: Mesh) -> Self {
+        Node {
+            mesh: Some(mesh),
+            ..Node::new(name)
+        }
+    }
+    pub fn parent(&self) -> Option<NodeId> {
+        self.parent
+    }
+    pub fn children(&self) -> &[NodeId] {
+        &self.children
+    }
+}
+
+///A tree of nodes, each with a transform relative to its parent and optionally a mesh. Give it to a Renderer (as its ```scene```) to draw every visible mesh in it.
+/// # Example
+/// ```
+/// # use ascii_renderer::prelude::*;
+/// let mut scene = Scene::new();
+/// let planet = scene.add(None, Node::with_mesh("planet", ascii_renderer::create_cube()));
+/// let mut moon = Node::with_mesh("moon", ascii_renderer::create_cube());
+/// moon.transform.position = vec3!(4.0, 0.0, 0.0);
+/// moon.transform.scale = vec3!(0.3, 0.3, 0.3);
+/// let moon = scene.add(Some(planet), moon);
+///
+/// // * Turning the planet swings the moon around with it
+/// scene[planet].transform.rotation = Quaternion::from_axis_angle(vec3!(0.0, 1.0, 0.0), std::f32::consts::PI);
+/// let moon_position = scene.world_matrix(moon).unwrap().transform_point(vec3!(0.0, 0.0, 0.0));
+/// assert!(moon_position.approx_eq(vec3!(-4.0, 0.0, 0.0), 0.001));
+/// assert_eq!(scene.find("moon"), Some(moon));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Scene {
+    // Removed nodes leave a gap, so that handles are never reused
+    nodes: Vec<Option<Node>>,
+    roots: Vec<NodeId>,
+}
+
+impl Scene {
+    pub fn new() -> Self {
+        Scene::default()
+    }
+    pub fn add(&mut self, parent: Option<NodeId>, mut node: Node) -> NodeId {
+        //! Adds a node as the last child of parent, or as a root if parent is None (or has been removed). Any children the node had are dropped, since they belong to the scene it came from.
+        let id = NodeId(self.nodes.len());
+        let parent = parent.filter(|&parent| self.get(parent).is_some());
+        node.parent = parent;
+        node.children = vec![];
+        self.nodes.push(Some(node));
+        self.children_of_mut(parent).push(id);
+        id
+    }
+    pub fn remove(&mut self, id: NodeId) -> Option<Node> {
+        //! Removes a node along with all of its children, returning the node.
+        let node = self.nodes.get_mut(id.0)?.take()?;
+        self.children_of_mut(node.parent)
+            .retain(|&child| child != id);
+        let mut stack = node.children.clone();
+        while let Some(child) = stack.pop() {
+            if let Some(child) = self.nodes[child.0].take() {
+                stack.extend(child.children);
+            }
+        }
+        Some(node)
+    }
+    pub fn get(&self, id: NodeId) -> Option<&Node> {
+        self.nodes.get(id.0)?.as_ref()
+    }
+    pub fn get_mut(&mut self, id: NodeId) -> Option<&mut Node> {
+        self.nodes.get_mut(id.0)?.as_mut()
+    }
+    pub fn find(&self, name: &str) -> Option<NodeId> {
+        //! Gets the first node with the given name, searching depth first in the order nodes were added to their parents.
+        self.iter()
+            .find(|(_, node)| node.name == name)
+            .map(|(id, _)| id)
+    }
+    pub fn roots(&self) -> &[NodeId] {
+        &self.roots
+    }
+    pub fn set_parent(&mut self, id: NodeId, parent: Option<NodeId>) -> bool {
+        //! Moves a node (and its children) to be the last child of parent, or a root if parent is None. Its transform stays relative to whichever node it ends up under.
+        //! Returns false and changes nothing if either node doesn't exist, or if parent is the node itself or one of its children.
+        if self.get(id).is_none() || parent.is_some_and(|parent| self.get(parent).is_none()) {
+            return false;
+        }
+        let mut ancestor = parent;
+        while let Some(current) = ancestor {
+            if current == id {
+                return false;
+            }
+            ancestor = self[current].parent;
+        }
+        let old_parent = self[id].parent;
+        self.children_of_mut(old_parent)
+            .retain(|&child| child != id);
+        self.children_of_mut(parent).push(id);
+        self[id].parent = parent;
+        true
+    }
+    pub fn world_matrix(&self, id: NodeId) -> Option<Matrix4> {
+        //! The matrix that takes points from the node's space to the world, combining the transforms of the node and all of its parents.
+        let mut node = self.get(id)?;
+        let mut matrix = node.transform.matrix();
+        while let Some(parent) = node.parent {
+            node = &self[parent];
+            matrix = node.transform.matrix() * matrix;
+        }
+        Some(matrix)
+    }
+    pub fn iter(&self) -> impl Iterator<Item = (NodeId, &Node)> {
+        //! Goes through every node depth first, parents before their children.
+        let mut stack: Vec<NodeId> = self.roots.iter().rev().copied().collect();
+        std::iter::from_fn(move || {
+            let id = stack.pop()?;
+            let node = &self[id];
+            stack.extend(node.children.iter().rev());
+            Some((id, node))
+        })
+    }
+    pub fn meshs(&self) -> Vec<(&Mesh, Matrix4)> {
+        //! Gets every mesh that should be drawn, along with the matrix that takes its vertices to the world (including the mesh's own position, rotation and scale).
+        self.mesh_nodes()
+            .into_iter()
+            .filter_map(|(node, matrix)| {
+                let mesh = node.mesh.as_ref()?;
+                Some((mesh, matrix * mesh.model_matrix()))
+            })
+            .collect()
+    }
+    /// Gets every visible node with a mesh, along with its world matrix (not including the mesh's own transform).
+    pub(crate) fn mesh_nodes(&self) -> Vec<(&Node, Matrix4)> {
+        let mut ret = vec![];
+        let mut stack: Vec<(NodeId, Matrix4)> = self
+            .roots
+            .iter()
+            .rev()
+            .map(|&root| (root, Matrix4::IDENTITY))
+            .collect();
+        while let Some((id, parent_matrix)) = stack.pop() {
+            let node = &self[id];
+            if !node.visible {
+                continue;
+            }
+            let matrix = parent_matrix * node.transform.matrix();
+            if node.mesh.is_some() {
+                ret.push((node, matrix));
+            }
+            stack.extend(node.children.iter().rev().map(|&child| (child, matrix)));
+        }
+        ret
+    }
+    fn children_of_mut(&mut self, parent: Option<NodeId>) -> &mut Vec<NodeId> {
+        match parent {
+            Some(parent) => &mut self[parent].children,
+            None => &mut self.roots,
+        }
+    }
+}
+
+impl std::ops::Index<NodeId> for Scene {
+    type Output = Node;
+    fn index(&self, id: NodeId) -> &Self::Output {
+        //! Panics if the node has been removed.
+        self.get(id).expect("node has been removed from the scene")
+    }
+}
+
+impl std::ops::IndexMut<NodeId> for Scene {
+    fn index_mut(&mut self, id: NodeId) -> &mut Self::Output {
+        self.get_mut(id)
+            .expect("node has been removed from the scene")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rendering::Quaternion;
+    use crate::{vec3, Vector3};
+
+    fn origin_of(scene: &Scene, id: NodeId) -> Vector3 {
+        scene
+            .world_matrix(id)
+            .unwrap()
+            .transform_point(vec3!(0.0, 0.0, 0.0))
+    }
+
+    #[test]
+    fn hierarchy() {
+        let mut scene = Scene::new();
+        let car = scene.add(None, Node::new("car"));
+        let mut wheel = Node::with_mesh("wheel", crate::create_cube());
+        wheel.transform.position = vec3!(1.0, 0.0, 0.0);
+        let wheel = scene.add(Some(car), wheel);
+        let hubcap = scene.add(Some(wheel), Node::new("hubcap"));
+        scene[hubcap].transform.position = vec3!(0.0, 0.0, 1.0);
+
+        scene[car].transform.position = vec3!(0.0, 5.0, 0.0);
+        scene[car].transform.scale = vec3!(2.0, 2.0, 2.0);
+        assert!(origin_of(&scene, hubcap).approx_eq(vec3!(2.0, 5.0, 2.0), 1e-4));
+        scene[wheel].transform.rotation =
+            Quaternion::from_axis_angle(vec3!(1.0, 0.0, 0.0), std::f32::consts::FRAC_PI_2);
+        assert!(origin_of(&scene, hubcap).approx_eq(vec3!(2.0, 3.0, 0.0), 1e-4));
+
+        let ids: Vec<NodeId> = scene.iter().map(|(id, _)| id).collect();
+        assert_eq!(ids, vec![car, wheel, hubcap]);
+        assert_eq!(scene.find("hubcap"), Some(hubcap));
+        assert_eq!(scene.find("trunk"), None);
+
+        // The mesh's own transform comes before the node's
+        scene
+            .get_mut(wheel)
+            .unwrap()
+            .mesh
+            .as_mut()
+            .unwrap()
+            .position = vec3!(0.0, 0.0, 1.0);
+        let meshs = scene.meshs();
+        assert_eq!(meshs.len(), 1);
+        let mesh_origin = meshs[0].1.transform_point(vec3!(0.0, 0.0, 0.0));
+        assert!(mesh_origin.approx_eq(origin_of(&scene, hubcap), 1e-4));
+
+        scene[car].visible = false;
+        assert!(scene.meshs().is_empty());
+    }
+
+    #[test]
+    fn reparenting_and_removal() {
+        let mut scene = Scene::new();
+        let a = scene.add(None, Node::new("a"));
+        let b = scene.add(Some(a), Node::new("b"));
+        let c = scene.add(Some(b), Node::new("c"));
+        scene[a].transform.position = vec3!(1.0, 0.0, 0.0);
+
+        // A node can't end up under itself
+        assert!(!scene.set_parent(a, Some(c)));
+        assert!(!scene.set_parent(a, Some(a)));
+
+        assert!(scene.set_parent(c, None));
+        assert_eq!(scene.roots(), &[a, c]);
+        assert_eq!(scene[b].children(), &[]);
+        assert!(origin_of(&scene, c).approx_eq(vec3!(0.0, 0.0, 0.0), 1e-4));
+        assert!(scene.set_parent(c, Some(b)));
+        assert_eq!(scene[c].parent(), Some(b));
+
+        assert_eq!(scene.remove(b).unwrap().name, "b");
+        assert!(scene.get(c).is_none());
+        assert!(scene[a].children().is_empty());
+        assert!(scene.remove(b).is_none());
+        // Handles aren't reused
+        let d = scene.add(Some(b), Node::new("d"));
+        assert!(d != b && d != c);
+        assert_eq!(scene.roots(), &[a, d]);
+    }
+}
@@ -0,0 +1,339 @@
+/*!
+Loading meshs from STL files, the format most CAD programs and 3D printing tools export. Both ASCII and binary STL files are supported, and which one a file is gets worked out from its contents.
+STL files store every triangle with its own copies of its corners, so corners at exactly the same position are merged into one vertex. Every triangle becomes a face, and its outline becomes edges (each shared edge only once). Since everything is made of triangles, the flat sides of CAD models are drawn with the diagonals across them, which the renderer's ```feature_edges``` can hide.
+The facet normals in the file are ignored, since plenty of programs leave them zeroed, and meshs are given the solid's name (for ASCII files) as their ```object```.
+# Example
+```no_run
+# use ascii_renderer::prelude::*;
+let mut part = ascii_renderer::stl::load("bracket.stl").unwrap();
+part.recenter();
+```
+*/
+use super::rendering::Mesh;
+use super::{vec3, Vector3};
+use std::collections::{HashMap, HashSet};
+
+/// The size of a binary STL file's header, which is followed by the number of triangles as a u32.
+const HEADER_LEN: usize = 80;
+/// The size of each triangle of a binary STL file: a normal and three corners, each three f32s, followed by two bytes of attributes.
+const TRIANGLE_LEN: usize = 50;
+
+#[derive(Debug)]
+pub enum StlError {
+    Io(std::io::Error),
+    /// A binary file is too short to hold the number of triangles it says it has.
+    Truncated {
+        triangles: usize,
+        len: usize,
+    },
+    /// A line of an ASCII file (counting from 1) isn't what was expected there.
+    Syntax {
+        line: usize,
+        found: String,
+    },
+    /// A number in an ASCII file couldn't be read.
+    InvalidNumber {
+        line: usize,
+        text: String,
+    },
+}
+
+impl std::fmt::Display for StlError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StlError::Io(error) => write!(f, "{error}"),
+            StlError::Truncated { triangles, len } => write!(
+                f,
+                "binary STL file says it has {triangles} triangles, but is only {len} bytes long"
+            ),
+            StlError::Syntax { line, found } => {
+                write!(f, "unexpected \"{found}\" on line {line} of STL file")
+            }
+            StlError::InvalidNumber { line, text } => {
+                write!(f, "invalid number \"{text}\" on line {line} of STL file")
+            }
+        }
+    }
+}
+
+impl std::error::Error for StlError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            StlError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl std::convert::From<std::io::Error> for StlError {
+    fn from(error: std::io::Error) -> Self {
+        StlError::Io(error)
+    }
+}
+
+pub fn load(path: impl AsRef<std::path::Path>) -> Result<Mesh, StlError> {
+    //! Reads an ASCII or binary STL file into a mesh.
+    parse(&std::fs::read(path)?)
+}
+
+pub fn parse(bytes: &[u8]) -> Result<Mesh, StlError> {
+    //! Reads the contents of an ASCII or binary STL file into a mesh.
+    //! Binary files can start with "solid" too, so a file is only read as ASCII if it does and isn't exactly the size of a binary file.
+    let binary_len = bytes
+        .get(HEADER_LEN..HEADER_LEN + 4)
+        .map(|count| HEADER_LEN + 4 + triangle_count(count) * TRIANGLE_LEN);
+    let ascii = bytes.trim_ascii_start().starts_with(b"solid");
+    if ascii && binary_len != Some(bytes.len()) {
+        parse_ascii(&String::from_utf8_lossy(bytes))
+    } else {
+        parse_binary(bytes)
+    }
+}
+
+fn triangle_count(bytes: &[u8]) -> usize {
+    u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize
+}
+
+fn parse_binary(bytes: &[u8]) -> Result<Mesh, StlError> {
+    let Some(count) = bytes.get(HEADER_LEN..HEADER_LEN + 4) else {
+        return Err(StlError::Truncated {
+            triangles: 0,
+            len: bytes.len(),
+        });
+    };
+    let triangles = triangle_count(count);
+    let body = &bytes[HEADER_LEN + 4..];
+    if body.len() < triangles * TRIANGLE_LEN {
+        return Err(StlError::Truncated {
+            triangles,
+            len: bytes.len(),
+        });
+    }
+
+    let float = |bytes: &[u8]| f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    let mut builder = Builder::default();
+    for triangle in body.chunks_exact(TRIANGLE_LEN).take(triangles) {
+        // Skips the normal, which takes up the first 12 bytes
+        let corner = |i: usize| {
+            let start = 12 + i * 12;
+            vec3!(
+                float(&triangle[start..]),
+                float(&triangle[start + 4..]),
+                float(&triangle[start + 8..])
+            )
+        };
+        builder.add_triangle([corner(0), corner(1), corner(2)]);
+    }
+    Ok(builder.mesh)
+}
+
+fn parse_ascii(text: &str) -> Result<Mesh, StlError> {
+    let mut builder = Builder::default();
+    let mut corners: Vec<Vector3> = vec![];
+    let mut in_facet = false;
+    let mut lines = 0;
+    for (i, line) in text.lines().enumerate() {
+        lines = i + 1;
+        let syntax_error = || StlError::Syntax {
+            line: i + 1,
+            found: line.trim().to_owned(),
+        };
+        let mut words = line.split_whitespace();
+        match (words.next(), in_facet) {
+            (None, _) => (),
+            (Some("solid"), false) => {
+                let name = words.collect::<Vec<&str>>().join(" ");
+                if builder.mesh.object.is_none() && !name.is_empty() {
+                    builder.mesh.object = Some(name);
+                }
+            }
+            (Some("endsolid"), false) => (),
+            (Some("facet"), false) => in_facet = true,
+            (Some("outer" | "endloop"), true) => (),
+            (Some("vertex"), true) => {
+                let mut coordinate = || {
+                    let text = words.next().ok_or_else(syntax_error)?;
+                    text.parse::<f32>().map_err(|_| StlError::InvalidNumber {
+                        line: i + 1,
+                        text: text.to_owned(),
+                    })
+                };
+                corners.push(vec3!(coordinate()?, coordinate()?, coordinate()?));
+            }
+            (Some("endfacet"), true) => {
+                if corners.len() < 3 {
+                    return Err(syntax_error());
+                }
+                //Some programs write polygons with more than three corners, which are split into triangles as a fan
+                for i in 1..corners.len() - 1 {
+                    builder.add_triangle([corners[0], corners[i], corners[i + 1]]);
+                }
+                corners.clear();
+                in_facet = false;
+            }
+            _ => return Err(syntax_error()),
+        }
+    }
+    if in_facet {
+        return Err(StlError::Syntax {
+            line: lines + 1,
+            found: "end of file".to_owned(),
+        });
+    }
+    Ok(builder.mesh)
+}
+
+/// Builds a mesh out of triangles, merging corners at the same position and edges shared by several triangles.
+#[derive(Default)]
+struct Builder {
+    mesh: Mesh,
+    vertices: HashMap<[u32; 3], usize>,
+    edges: HashSet<(usize, usize)>,
+}
+
+impl Builder {
+    fn add_triangle(&mut self, corners: [Vector3; 3]) {
+        let [a, b, c] = corners.map(|corner| self.vertex(corner));
+        if a == b || b == c || c == a {
+            return; //Triangles with merged corners have no area
+        }
+        self.mesh.add_face((a, b, c));
+        for (from, to) in [(a, b), (b, c), (c, a)] {
+            if self.edges.insert((from.min(to), from.max(to))) {
+                self.mesh.add_edge((from, to));
+            }
+        }
+    }
+    fn vertex(&mut self, position: Vector3) -> usize {
+        // Adding 0.0 turns -0.0 into 0.0, so that the two are merged
+        let key = [position.x, position.y, position.z].map(|x| (x + 0.0).to_bits());
+        let next = self.vertices.len();
+        *self.vertices.entry(key).or_insert_with(|| {
+            self.mesh.insert_vertex(next, position);
+            next
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(name: &str) -> std::path::PathBuf {
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures")
+            .join(name)
+    }
+
+    fn check_cube(cube: &Mesh) {
+        assert_eq!(cube.get_verticies().len(), 8);
+        assert_eq!(cube.get_faces().len(), 12);
+        // The 12 sides of the cube and a diagonal across each of its 6 sides
+        assert_eq!(cube.get_edges().len(), 18);
+        assert!(cube
+            .get_verticies()
+            .values()
+            .all(|v| [v.x, v.y, v.z].iter().all(|&x| x == 0.0 || x == 1.0)));
+        // Every face points away from the middle of the cube
+        let middle = vec3!(0.5, 0.5, 0.5);
+        for &(a, b, c) in cube.get_faces() {
+            let [a, b, c] = [a, b, c].map(|i| cube.get_verticies()[&i]);
+            assert!((b - a).cross(c - a).dot(a - middle) > 0.0);
+        }
+    }
+
+    #[test]
+    fn ascii_and_binary() {
+        let ascii = load(fixture("cube_ascii.stl")).unwrap();
+        check_cube(&ascii);
+        assert_eq!(ascii.object.as_deref(), Some("cube"));
+
+        let binary = load(fixture("cube_binary.stl")).unwrap();
+        check_cube(&binary);
+        assert_eq!(binary.object, None);
+        assert_eq!(binary.get_faces(), ascii.get_faces());
+        assert_eq!(binary.get_verticies(), ascii.get_verticies());
+
+        // A binary file whose header starts with "solid" is still read as binary
+        let mut bytes = std::fs::read(fixture("cube_binary.stl")).unwrap();
+        bytes[..5].copy_from_slice(b"solid");
+        check_cube(&parse(&bytes).unwrap());
+
+        assert!(matches!(load(fixture("missing.stl")), Err(StlError::Io(_))));
+    }
+
+    #[test]
+    fn errors() {
+        let bytes = std::fs::read(fixture("cube_binary.stl")).unwrap();
+        assert!(matches!(
+            parse(&bytes[..bytes.len() - 1]),
+            Err(StlError::Truncated { triangles: 12, .. })
+        ));
+        assert!(matches!(
+            parse(b"too short"),
+            Err(StlError::Truncated { triangles: 0, .. })
+        ));
+
+        let facet = |vertex: &str| {
+            format!("solid broken\nfacet normal 0 0 0\nouter loop\nvertex 0 0 0\nvertex 1 0 0\n{vertex}\nendloop\nendfacet\nendsolid")
+        };
+        assert!(parse(facet("vertex 0 1 0").as_bytes()).is_ok());
+        let error = parse(facet("vertex 0 one 0").as_bytes()).unwrap_err();
+        assert!(matches!(&error, StlError::InvalidNumber { line: 6, text } if text == "one"));
+        assert_eq!(
+            error.to_string(),
+            "invalid number \"one\" on line 6 of STL file"
+        );
+        assert!(matches!(
+            parse(facet("vertex 0 1").as_bytes()),
+            Err(StlError::Syntax { line: 6, .. })
+        ));
+        // Facets need at least three corners
+        assert!(matches!(
+            parse(facet("").as_bytes()),
+            Err(StlError::Syntax { line: 8, .. })
+        ));
+        assert!(matches!(
+            parse(b"solid broken\nfacet normal 0 0 0\nouter loop\n"),
+            Err(StlError::Syntax { line: 4, .. })
+        ));
+        assert!(matches!(
+            parse(b"solid broken\nvertex 0 0 0\n"),
+            Err(StlError::Syntax { line: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn merging() {
+        // Two triangles sharing a side, one with a corner at -0.0, and one with no area
+        let mesh = parse(
+            b"solid square
+facet normal 0 0 1
+outer loop
+vertex 0 0 0
+vertex 1 0 0
+vertex 1 1 0
+endloop
+endfacet
+facet normal 0 0 1
+outer loop
+vertex -0 0 0
+vertex 1 1 0
+vertex 0 1 0
+endloop
+endfacet
+facet normal 0 0 0
+outer loop
+vertex 0 1 0
+vertex 0 1 0
+vertex 1 1 0
+endloop
+endfacet
+endsolid square",
+        )
+        .unwrap();
+        assert_eq!(mesh.get_verticies().len(), 4);
+        assert_eq!(mesh.get_faces(), &vec![(0, 1, 2), (0, 2, 3)]);
+        assert_eq!(mesh.get_edges().len(), 5);
+    }
+}
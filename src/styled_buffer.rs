@@ -0,0 +1,378 @@
+use super::canvas::Canvas;
+use super::char_buffer::{CharBuffer, CharBufferError};
+use super::runner::Screen;
+use crossterm::cursor::MoveTo;
+use crossterm::style::{Attribute, Attributes, Color, SetBackgroundColor, SetForegroundColor};
+use crossterm::terminal::{Clear, ClearType};
+use std::io::Write;
+
+///How a cell is shown. None colors are the terminal's own colors.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Style {
+    pub foreground: Option<Color>,
+    pub background: Option<Color>,
+    ///Any mix of ```Attribute::Bold```, ```Attribute::Dim```, ```Attribute::Underlined```, ```Attribute::Reverse``` and the other crossterm attributes, e.g. ```Attribute::Bold | Attribute::Underlined```.
+    pub attributes: Attributes,
+}
+
+impl Style {
+    pub fn new(
+        foreground: Option<Color>,
+        background: Option<Color>,
+        attributes: Attributes,
+    ) -> Self {
+        Style {
+            foreground,
+            background,
+            attributes,
+        }
+    }
+}
+
+impl std::convert::From<Color> for Style {
+    fn from(color: Color) -> Self {
+        //! A style with the color as its foreground and nothing else.
+        Style {
+            foreground: Some(color),
+            ..Default::default()
+        }
+    }
+}
+
+///A char along with the style it is shown in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cell {
+    pub char: char,
+    pub style: Style,
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        //! A space with the default style.
+        Cell::new(' ', Style::default())
+    }
+}
+
+impl Cell {
+    pub fn new(char: char, style: Style) -> Self {
+        Cell { char, style }
+    }
+}
+
+///A CharBuffer where every cell also has a foreground color, background color and attributes. Lines, triangles and meshs drawn to it take the pen's style, and the renderer sets ```mesh_color``` to each mesh's color before drawing it, which replaces the pen's foreground for meshs that have one.
+/// A runner shows it when its logic implements ```Logic<StyledBuffer>```, only sending the escape sequences needed to change style between cells. Logic that doesn't need color should stick to the plain CharBuffer, which skips all of this.
+/// # Example
+/// ```
+/// # use ascii_renderer::prelude::*;
+/// let mut buf = StyledBuffer::new(10, 3);
+/// buf.pen = Color::Green.into();
+/// buf.draw_line(Line {
+///     stroke: Stroke::Char('+'),
+///     points: (vec2!(0.0, 2.0), vec2!(9.0, 2.0)),
+/// });
+/// buf.write_str(0, 0, "fps: 25", Style::new(None, None, Attribute::Bold.into()));
+/// assert_eq!(buf.get_cell(5, 2).unwrap().style.foreground, Some(Color::Green));
+/// assert_eq!(buf.get_char(0, 0), Some('f'));
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct StyledBuffer {
+    pub data: Vec<Vec<Cell>>,
+    pub dimensions: (usize, usize),
+    ///The style used for anything drawn through the Canvas trait.
+    pub pen: Style,
+    ///The color of the mesh currently being drawn, set through ```set_color()```. When set, it is used as the foreground instead of the pen's.
+    pub mesh_color: Option<Color>,
+    ///Whether a space is written after every char, the same as for a CharBuffer.
+    pub padded: bool,
+}
+
+impl StyledBuffer {
+    pub fn new(width: usize, height: usize) -> Self {
+        StyledBuffer {
+            data: vec![vec![Cell::default(); width]; height],
+            dimensions: (width, height),
+            pen: Style::default(),
+            mesh_color: None,
+            padded: true,
+        }
+    }
+    pub fn get_cell(&self, x: usize, y: usize) -> Option<Cell> {
+        self.data.get(y)?.get(x).copied()
+    }
+    pub fn get_char(&self, x: usize, y: usize) -> Option<char> {
+        Some(self.get_cell(x, y)?.char)
+    }
+    pub fn set_cell(&mut self, x: usize, y: usize, value: Cell) -> Result<(), CharBufferError> {
+        let row = self
+            .data
+            .get_mut(y)
+            .ok_or(CharBufferError::RowOutOfRange { row: y })?;
+        let item = row
+            .get_mut(x)
+            .ok_or(CharBufferError::ItemOutOfRange { index: x })?;
+        *item = value;
+        Ok(())
+    }
+    pub fn write_str(&mut self, x: usize, y: usize, text: &str, style: Style) {
+        //! Writes text across the buffer starting at x, y, one char per cell. Whatever runs off the buffer is cut off.
+        for (i, char) in text.chars().enumerate() {
+            let _ = self.set_cell(x + i, y, Cell::new(char, style));
+        }
+    }
+    pub fn resize(&mut self, width: usize, height: usize, clear: bool) {
+        //! Changes the dimensions of the buffer. If clear is false, whatever lies within both the old and new dimensions is kept and any new cells are blank, otherwise the whole buffer is blank afterwards.
+        if clear {
+            self.fill(Cell::default());
+        }
+        self.data.resize(height, vec![Cell::default(); width]);
+        for row in self.data.iter_mut() {
+            row.resize(width, Cell::default());
+        }
+        self.dimensions = (width, height);
+    }
+    pub fn fill(&mut self, cell: Cell) {
+        //! Filles the buffer with the given cell
+        for row in self.data.iter_mut() {
+            for item in row.iter_mut() {
+                *item = cell;
+            }
+        }
+    }
+    fn write_cell(&self, out: &mut dyn Write, cell: &Cell) -> std::io::Result<()> {
+        if self.padded {
+            write!(out, "{} ", cell.char)
+        } else {
+            write!(out, "{}", cell.char)
+        }
+    }
+}
+
+impl Clone for StyledBuffer {
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data.clone(),
+            dimensions: self.dimensions,
+            pen: self.pen,
+            mesh_color: self.mesh_color,
+            padded: self.padded,
+        }
+    }
+    fn clone_from(&mut self, source: &Self) {
+        //! Reuses the rows that are already allocated, the same as for a CharBuffer.
+        self.data.clone_from(&source.data);
+        self.dimensions = source.dimensions;
+        self.pen = source.pen;
+        self.mesh_color = source.mesh_color;
+        self.padded = source.padded;
+    }
+}
+
+impl std::convert::From<&CharBuffer> for StyledBuffer {
+    fn from(buffer: &CharBuffer) -> Self {
+        //! Copies the chars over with the default style.
+        StyledBuffer {
+            data: buffer
+                .data
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .map(|&char| Cell::new(char, Style::default()))
+                        .collect()
+                })
+                .collect(),
+            dimensions: buffer.dimensions,
+            pen: Style::default(),
+            mesh_color: None,
+            padded: buffer.padded,
+        }
+    }
+}
+
+impl Canvas for StyledBuffer {
+    fn dimensions(&self) -> (usize, usize) {
+        self.dimensions
+    }
+    fn plot(&mut self, x: usize, y: usize, char: char) {
+        let mut style = self.pen;
+        if let Some(color) = self.mesh_color {
+            style.foreground = Some(color);
+        }
+        let _ = self.set_cell(x, y, Cell::new(char, style));
+    }
+    fn char_at(&self, x: usize, y: usize) -> Option<char> {
+        self.get_char(x, y)
+    }
+    fn set_color(&mut self, color: Option<Color>) {
+        //! Meshs without a color go back to being drawn with the pen's own foreground.
+        self.mesh_color = color;
+    }
+}
+
+impl Screen for StyledBuffer {
+    fn new(width: usize, height: usize) -> Self {
+        StyledBuffer::new(width, height)
+    }
+    fn resize(&mut self, width: usize, height: usize, clear: bool) {
+        StyledBuffer::resize(self, width, height, clear);
+    }
+    fn size_for_terminal(&self, columns: u16, rows: u16) -> (usize, usize) {
+        let columns_per_cell = if self.padded { 2 } else { 1 };
+        (
+            columns as usize / columns_per_cell,
+            rows.saturating_sub(1) as usize,
+        )
+    }
+    fn write_to(&self, out: &mut dyn Write) -> std::io::Result<()> {
+        //! Writes the buffer in the same layout as a CharBuffer, with a space after every char unless it is unpadded. Style changes are only sent when a cell's style differs from the one before it, and everything is reset at the end of any row that changed it.
+        for row in self.data.iter() {
+            let mut current = Style::default();
+            for cell in row.iter() {
+                write_style_change(out, &mut current, cell.style)?;
+                self.write_cell(out, cell)?;
+            }
+            if current != Style::default() {
+                write!(out, "{}", Attribute::Reset)?;
+            }
+            writeln!(out)?;
+        }
+        Ok(())
+    }
+    fn write_changes(&self, previous: &Self, out: &mut dyn Write) -> std::io::Result<()> {
+        //! Writes each changed cell in place, only moving the cursor when the next changed cell isn't right after the last one written. Style changes are sent the same way as for a whole frame, and reset once at the end. If the size or padding changed since previous, the terminal is cleared and the whole buffer is written instead.
+        if self.dimensions != previous.dimensions || self.padded != previous.padded {
+            // The last frame may have covered parts of the terminal that this one doesn't
+            write!(out, "{}", Clear(ClearType::All))?;
+            return self.redraw(out);
+        }
+        let mut current = Style::default();
+        let mut cursor = None;
+        for (y, (row, previous_row)) in self.data.iter().zip(previous.data.iter()).enumerate() {
+            for (x, (cell, previous_cell)) in row.iter().zip(previous_row.iter()).enumerate() {
+                if cell == previous_cell {
+                    continue;
+                }
+                if cursor != Some((x, y)) {
+                    let column = if self.padded { x * 2 } else { x };
+                    write!(out, "{}", MoveTo(column as u16, y as u16))?;
+                }
+                write_style_change(out, &mut current, cell.style)?;
+                self.write_cell(out, cell)?;
+                cursor = Some((x + 1, y));
+            }
+        }
+        if current != Style::default() {
+            write!(out, "{}", Attribute::Reset)?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes the sequences needed to go from the current style to the new one and updates current to match.
+fn write_style_change(out: &mut dyn Write, current: &mut Style, new: Style) -> std::io::Result<()> {
+    if new.attributes != current.attributes {
+        let changed = current.attributes ^ new.attributes;
+        let removed = current.attributes & changed;
+        let mut added = new.attributes & changed;
+        if !removed.is_empty() {
+            // Attributes can't be turned off one by one on every terminal, so start over from a full reset
+            write!(out, "{}", Attribute::Reset)?;
+            *current = Style::default();
+            added = new.attributes;
+        }
+        for attribute in Attribute::iterator().filter(|&attribute| added.has(attribute)) {
+            write!(out, "{attribute}")?;
+        }
+        current.attributes = new.attributes;
+    }
+    if new.foreground != current.foreground {
+        write!(
+            out,
+            "{}",
+            SetForegroundColor(new.foreground.unwrap_or(Color::Reset))
+        )?;
+        current.foreground = new.foreground;
+    }
+    if new.background != current.background {
+        write!(
+            out,
+            "{}",
+            SetBackgroundColor(new.background.unwrap_or(Color::Reset))
+        )?;
+        current.background = new.background;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn output(buf: &StyledBuffer) -> String {
+        let mut out = vec![];
+        buf.write_to(&mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn plain_cells_have_no_escapes() {
+        let mut buf = StyledBuffer::new(3, 2);
+        buf.write_str(1, 1, "ab", Style::default());
+        assert_eq!(&output(&buf), "      \n  a b \n");
+    }
+
+    #[test]
+    fn minimal_style_changes() {
+        let mut buf = StyledBuffer::new(4, 1);
+        let red = Style::from(Color::Red);
+        let bold_red = Style::new(Some(Color::Red), None, Attribute::Bold.into());
+        buf.write_str(0, 0, "ab", red);
+        buf.write_str(2, 0, "c", bold_red);
+        buf.write_str(3, 0, "d", red);
+        let expected = format!(
+            "{}a b {}c {}{}d {}\n",
+            SetForegroundColor(Color::Red),
+            Attribute::Bold,
+            Attribute::Reset,
+            SetForegroundColor(Color::Red),
+            Attribute::Reset,
+        );
+        assert_eq!(output(&buf), expected);
+    }
+
+    #[test]
+    fn only_changed_cells_are_written() {
+        let previous = StyledBuffer::new(3, 2);
+        let mut buf = previous.clone();
+        buf.write_str(1, 0, "ab", Color::Red.into());
+        buf.write_str(0, 1, "c", Style::default());
+        let mut out = vec![];
+        buf.write_changes(&previous, &mut out).unwrap();
+        let expected = format!(
+            "{}{}a b {}{}c ",
+            MoveTo(2, 0),
+            SetForegroundColor(Color::Red),
+            MoveTo(0, 1),
+            SetForegroundColor(Color::Reset),
+        );
+        assert_eq!(String::from_utf8(out).unwrap(), expected);
+    }
+
+    #[test]
+    fn plotting_uses_the_pen() {
+        let mut buf = StyledBuffer::new(2, 1);
+        buf.pen.attributes = Attribute::Underlined.into();
+        buf.set_color(Some(Color::Blue));
+        buf.plot(0, 0, '#');
+        buf.set_color(None);
+        buf.plot(1, 0, '+');
+        buf.plot(5, 5, '+');
+        let cell = buf.get_cell(0, 0).unwrap();
+        assert_eq!(cell.style.foreground, Some(Color::Blue));
+        assert!(cell.style.attributes.has(Attribute::Underlined));
+        let cell = buf.get_cell(1, 0).unwrap();
+        assert_eq!(cell.char, '+');
+        assert_eq!(cell.style.foreground, None);
+        assert!(cell.style.attributes.has(Attribute::Underlined));
+        assert_eq!(buf.pen.foreground, None);
+    }
+}
@@ -0,0 +1,143 @@
+use crossterm::{
+    cursor::{Hide, Show},
+    event::{
+        DisableMouseCapture, EnableMouseCapture, KeyboardEnhancementFlags,
+        PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+    },
+    queue,
+    terminal::{self, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Once;
+
+// What the current session changed, so that whichever of drop or the panic hook gets there first can undo it
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+static ALTERNATE_SCREEN: AtomicBool = AtomicBool::new(false);
+static RAW_MODE: AtomicBool = AtomicBool::new(false);
+static KEYBOARD_ENHANCED: AtomicBool = AtomicBool::new(false);
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+static INSTALL_PANIC_HOOK: Once = Once::new();
+
+///A guard that sets the terminal up for drawing frames and puts it back the way it was when dropped. The runner starts one for the length of ```run()```, so this is only needed when stepping a runner by hand.
+/// While it is alive, panics restore the terminal before the panic message is printed. Once ```install_signal_handler()``` has been called (```run()``` does this itself), Ctrl-C sets the flag read by ```interrupted()```, and a second Ctrl-C puts the terminal back and kills the program. In raw mode Ctrl-C arrives as a key event instead. Only one session should be alive at a time.
+/// # Example
+/// ```no_run
+/// # use ascii_renderer::prelude::*;
+/// # struct MyLogic;
+/// # impl Logic for MyLogic {
+/// #     fn process(&mut self, _screen_buf: &mut CharBuffer, _delta: f32) -> ProcessReturn {
+/// #         ProcessReturn::Continue
+/// #     }
+/// # }
+/// let mut runner = Runner::new(50, 50, 25, MyLogic);
+/// let _ = TerminalSession::install_signal_handler();
+/// let session = TerminalSession::start(true, false).unwrap();
+/// while !session.interrupted() {
+///     if let ProcessReturn::End = runner.step(true) {
+///         break;
+///     }
+/// }
+/// drop(session); //The terminal is back to normal, and the cursor is shown again
+/// ```
+#[derive(Debug)]
+pub struct TerminalSession {
+    _private: (),
+}
+
+impl TerminalSession {
+    pub fn start(alternate_screen: bool, raw_mode: bool) -> std::io::Result<Self> {
+        //! Hides the cursor, switches to the alternate screen if alternate_screen is true (so whatever was in the terminal before comes back afterwards), and enables raw mode along with mouse capture and key release reporting (where supported) if raw_mode is true.
+        INSTALL_PANIC_HOOK.call_once(install_panic_hook);
+        INTERRUPTED.store(false, Ordering::SeqCst);
+        ACTIVE.store(true, Ordering::SeqCst);
+
+        let mut stdout = std::io::stdout();
+        if alternate_screen {
+            queue!(stdout, EnterAlternateScreen)?;
+            ALTERNATE_SCREEN.store(true, Ordering::SeqCst);
+        }
+        queue!(stdout, Hide)?;
+        if raw_mode {
+            terminal::enable_raw_mode()?;
+            RAW_MODE.store(true, Ordering::SeqCst);
+            queue!(stdout, EnableMouseCapture)?;
+            if terminal::supports_keyboard_enhancement().unwrap_or(false) {
+                queue!(
+                    stdout,
+                    PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::REPORT_EVENT_TYPES)
+                )?;
+                KEYBOARD_ENHANCED.store(true, Ordering::SeqCst);
+            }
+        }
+        stdout.flush()?;
+        Ok(TerminalSession { _private: () })
+    }
+    pub fn interrupted(&self) -> bool {
+        //! Whether Ctrl-C has been pressed since the session started. This only ever becomes true once ```install_signal_handler()``` has been called, and in raw mode Ctrl-C arrives as a key event instead, so it stays false then too.
+        INTERRUPTED.load(Ordering::SeqCst)
+    }
+    pub fn install_signal_handler() -> Result<(), ctrlc::Error> {
+        //! Makes the first Ctrl-C during a session set the flag read by ```interrupted()```, so that a runner ends normally and puts the terminal back. Outside a session, or if Ctrl-C is pressed again before the program has stopped, the terminal is put back and the program is killed as it would have been without the handler.
+        //! ```Runner::run()``` calls this, so it is only needed when stepping a runner by hand. Fails if the program already has a Ctrl-C handler (including this one), in which case it is left as it is.
+        ctrlc::set_handler(handle_interrupt)
+    }
+}
+
+impl Drop for TerminalSession {
+    fn drop(&mut self) {
+        restore();
+    }
+}
+
+fn install_panic_hook() {
+    let previous_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        restore();
+        previous_hook(info);
+    }));
+}
+
+fn handle_interrupt() {
+    if ACTIVE.load(Ordering::SeqCst) && !INTERRUPTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    restore();
+    kill_by_interrupt();
+}
+
+/// Ends the program the way Ctrl-C would have without a handler, so the parent still sees it as interrupted.
+#[cfg(unix)]
+fn kill_by_interrupt() {
+    unsafe {
+        libc::signal(libc::SIGINT, libc::SIG_DFL);
+        libc::raise(libc::SIGINT);
+    }
+    std::process::exit(130);
+}
+
+#[cfg(not(unix))]
+fn kill_by_interrupt() {
+    std::process::exit(130);
+}
+
+/// Undoes whatever the active session changed. Does nothing if there is no active session, so it is safe to call more than once.
+fn restore() {
+    if !ACTIVE.swap(false, Ordering::SeqCst) {
+        return;
+    }
+    // Errors are ignored, since this also runs while panicking and there is nothing better to do with them
+    let mut stdout = std::io::stdout();
+    if KEYBOARD_ENHANCED.swap(false, Ordering::SeqCst) {
+        let _ = queue!(stdout, PopKeyboardEnhancementFlags);
+    }
+    if RAW_MODE.swap(false, Ordering::SeqCst) {
+        let _ = queue!(stdout, DisableMouseCapture);
+        let _ = terminal::disable_raw_mode();
+    }
+    let _ = queue!(stdout, Show);
+    if ALTERNATE_SCREEN.swap(false, Ordering::SeqCst) {
+        let _ = queue!(stdout, LeaveAlternateScreen);
+    }
+    let _ = stdout.flush();
+}
@@ -0,0 +1,109 @@
+use super::canvas::Canvas;
+use super::depth_buffer::DepthBuffer;
+use super::Vector2;
+
+/// The struct fed to a Canvas for filling triangles. Each point has a matching depth, which is interpolated across the triangle for depth testing.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Triangle {
+    pub char: char,
+    pub points: (Vector2, Vector2, Vector2),
+    pub depths: (f32, f32, f32),
+}
+
+pub(crate) fn draw_triangle_depth_tested<C: Canvas + ?Sized>(
+    canvas: &mut C,
+    triangle: Triangle,
+    depth_buffer: &mut DepthBuffer,
+) {
+    //! Fills every cell whose center lies within the triangle, as long as the interpolated depth at that cell is closer than what the depth buffer already holds.
+    let (a, b, c) = triangle.points;
+    let area = edge_function(a, b, c);
+    if area == 0.0 || area.is_nan() {
+        return;
+    }
+
+    let min_x = a.x.min(b.x).min(c.x).floor().max(0.0);
+    let min_y = a.y.min(b.y).min(c.y).floor().max(0.0);
+    let max_x =
+        a.x.max(b.x)
+            .max(c.x)
+            .ceil()
+            .min(canvas.dimensions().0 as f32 - 1.0);
+    let max_y =
+        a.y.max(b.y)
+            .max(c.y)
+            .ceil()
+            .min(canvas.dimensions().1 as f32 - 1.0);
+    if min_x > max_x || min_y > max_y {
+        return;
+    }
+
+    for y in min_y as usize..=max_y as usize {
+        for x in min_x as usize..=max_x as usize {
+            let point = Vector2::new(x as f32, y as f32);
+            // Barycentric weights, all of them share the sign of the area when the point is inside
+            let weights = (
+                edge_function(b, c, point) / area,
+                edge_function(c, a, point) / area,
+                edge_function(a, b, point) / area,
+            );
+            if weights.0 < 0.0 || weights.1 < 0.0 || weights.2 < 0.0 {
+                continue;
+            }
+            let depth = triangle.depths.0 * weights.0
+                + triangle.depths.1 * weights.1
+                + triangle.depths.2 * weights.2;
+            if depth_buffer.test_and_set(x, y, depth) {
+                canvas.plot(x, y, triangle.char);
+            }
+        }
+    }
+}
+
+/// Twice the signed area of the triangle (a, b, point). Positive when the point is on one side of a->b, negative on the other.
+#[inline]
+fn edge_function(a: Vector2, b: Vector2, point: Vector2) -> f32 {
+    (b.x - a.x) * (point.y - a.y) - (b.y - a.y) * (point.x - a.x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::char_buffer::CharBuffer;
+    use crate::vec2;
+
+    #[test]
+    fn triangle_fill() {
+        let mut buf = CharBuffer::new(4, 4);
+        let mut depth_buf = DepthBuffer::new(4, 4);
+        buf.draw_triangle_depth_tested(
+            Triangle {
+                char: '#',
+                points: (vec2!(0.0, 0.0), vec2!(3.0, 0.0), vec2!(0.0, 3.0)),
+                depths: (1.0, 1.0, 1.0),
+            },
+            &mut depth_buf,
+        );
+        assert_eq!(&buf.to_string(), "# # # # \n# # #   \n# #     \n#       \n");
+    }
+
+    #[test]
+    fn triangle_depth_testing() {
+        let mut buf = CharBuffer::new(4, 4);
+        let mut depth_buf = DepthBuffer::new(4, 4);
+        let near = Triangle {
+            char: 'n',
+            points: (vec2!(0.0, 0.0), vec2!(3.0, 0.0), vec2!(0.0, 3.0)),
+            depths: (1.0, 1.0, 1.0),
+        };
+        let far = Triangle {
+            char: 'f',
+            points: (vec2!(3.0, 3.0), vec2!(0.0, 3.0), vec2!(3.0, 0.0)),
+            depths: (2.0, 2.0, 2.0),
+        };
+        buf.draw_triangle_depth_tested(near, &mut depth_buf);
+        buf.draw_triangle_depth_tested(far, &mut depth_buf);
+        assert_eq!(buf.get_char(1, 2), Some('n'));
+        assert_eq!(buf.get_char(3, 3), Some('f'));
+    }
+}